@@ -1,9 +1,16 @@
 // local
+use crate::api::{bad_request, filter_error_response};
+use crate::model::error_response::ErrorResponse;
 use crate::model::filter::FilterRequest;
-use crate::model::filter::FilteredLogResponse;
+use crate::model::filter::{FilteredLogResponse, Hunk};
+use crate::model::log_line::LogLine;
+use crate::parsing::context::{ContextEvent, ContextWindow};
+use crate::parsing::entry::parse_entries;
+use crate::parsing::filter::Filter;
+use crate::parsing::format::resolve_format;
 use crate::parsing::parser::parse_log;
 // axum
-use axum::{extract::Json as AxumJson, routing::post, Json, Router};
+use axum::{extract::Json as AxumJson, response::IntoResponse, routing::post, Json, Router};
 
 pub fn router() -> Router {
     Router::new().route("/filter", post(filter_handler))
@@ -15,36 +22,52 @@ pub fn router() -> Router {
     path = "/filter",
     request_body = FilterRequest,
     responses(
-        (status = 200, description = "Filtered log lines with summary", body = FilteredLogResponse)
+        (status = 200, description = "Filtered log entries grouped into hunks with their context, and a summary", body = FilteredLogResponse),
+        (status = 400, description = "Invalid query, unknown log format, invalid field mapping, keyword regex, field filter or time window. Query errors include the position they refer to", body = ErrorResponse),
+        (status = 422, description = "Unknown minimum log level")
     )
 )]
-pub async fn filter_handler(AxumJson(req): AxumJson<FilterRequest>) -> Json<FilteredLogResponse> {
-    let summary = parse_log(&req.log_text, req.domains.as_ref(), req.levels.as_ref());
+pub async fn filter_handler(AxumJson(req): AxumJson<FilterRequest>) -> impl IntoResponse {
+    let format = match resolve_format(req.format.as_deref(), req.field_mapping.as_ref()) {
+        Ok(format) => format,
+        Err(e) => return bad_request(e),
+    };
 
-    // Also collect the filtered lines
-    let lines: Vec<String> = req
-        .log_text
-        .lines()
-        .filter(|line| {
-            if let Some(caps) = crate::parsing::parser::LOG_REGEX.captures(line) {
-                let level = &caps["level"];
-                let domain = &caps["domain"];
-                req.levels
-                    .as_ref()
-                    .map_or(true, |l| l.contains(&level.to_string()))
-                    && req
-                        .domains
-                        .as_ref()
-                        .map_or(true, |d| d.contains(&domain.to_string()))
-            } else {
-                false
+    // The log is parsed once, the entries are needed for the time span, the summary and the
+    // hunks
+    let entries = parse_entries(format.as_ref(), &req.log_text);
+
+    // Relative times are resolved against the time span of the whole log
+    let times = entries.iter().filter_map(|entry| entry.record.time);
+    let filter = match Filter::compile(&req.filter_spec(), times.clone().min(), times.max()) {
+        Ok(filter) => filter,
+        Err(e) => return filter_error_response(e),
+    };
+
+    let summary = parse_log(&entries, &filter);
+
+    // Also collect the filtered entries with their context, excluded entries are never shown
+    let (before, after) = req.context_window();
+    let mut window = ContextWindow::new(before, after);
+    let mut hunks: Vec<Hunk> = Vec::new();
+    for entry in entries {
+        if filter.excludes(&entry.record) {
+            continue;
+        }
+        let matched = filter.matches(&entry.record);
+        for event in window.push(entry, matched) {
+            match event {
+                ContextEvent::Line { entry, context } => {
+                    let line = LogLine::new(entry, context, &filter);
+                    match hunks.last_mut() {
+                        Some(hunk) => hunk.lines.push(line),
+                        None => hunks.push(Hunk { lines: vec![line] }),
+                    }
+                }
+                ContextEvent::Separator => hunks.push(Hunk { lines: Vec::new() }),
             }
-        })
-        .map(|s| s.to_string())
-        .collect();
+        }
+    }
 
-    Json(FilteredLogResponse {
-        filtered_lines: lines,
-        summary,
-    })
+    Json(FilteredLogResponse { hunks, summary }).into_response()
 }
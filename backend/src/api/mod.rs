@@ -1,3 +1,42 @@
 pub mod filter;
+pub mod seek;
+pub mod sessions;
 pub mod stream_filtered_logs;
 pub mod upload;
+
+// local
+use crate::model::error_response::ErrorResponse;
+use crate::parsing::filter::FilterError;
+// axum
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+/// Report an invalid request, all 400 responses carry an [`ErrorResponse`]
+pub fn bad_request(error: impl Into<String>) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: error.into(),
+            position: None,
+        }),
+    )
+        .into_response()
+}
+
+/// Report an invalid filter, query errors include the position they refer to
+pub fn filter_error_response(error: FilterError) -> Response {
+    match error {
+        FilterError::Query(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.message,
+                position: Some(e.position),
+            }),
+        )
+            .into_response(),
+        FilterError::Invalid(e) => bad_request(e),
+    }
+}
@@ -0,0 +1,145 @@
+// local
+use crate::api::bad_request;
+use crate::log_storage::{get_user_log, LogSource, UserLog};
+use crate::model::error_response::ErrorResponse;
+use crate::model::seek_response::SeekResponse;
+use crate::parsing::entry::{EntryAssembler, LogEntry};
+use crate::parsing::time_range::TimeRange;
+// axum
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+// chrono
+use chrono::{DateTime, Utc};
+// serde
+use serde::Deserialize;
+// tokio
+use tokio::fs::File;
+use tokio::io::{AsyncBufReadExt, AsyncSeekExt, BufReader};
+// tracing
+use tracing::{debug, error};
+// std
+use std::io::SeekFrom;
+
+pub fn router() -> Router {
+    Router::new().route("/sessions/{id}/seek", get(seek_handler))
+}
+
+#[derive(Deserialize)]
+pub struct SeekQuery {
+    /// Absolute timestamp or relative to the start (`+30s`) or end (`-15m`) of the log
+    pub ts: String,
+}
+
+/// Resolve a timestamp given by a client against the time span of the log
+pub fn resolve_time(user_log: &UserLog, text: &str) -> Result<DateTime<Utc>, String> {
+    TimeRange::parse(Some(text), None, user_log.start_time, user_log.stop_time)?
+        .from
+        .ok_or_else(|| "Missing timestamp".to_string())
+}
+
+/// First entry of a file at or after `time`. Only the entries from the index block that may
+/// contain it onwards are read, entries without a parsed timestamp are skipped.
+pub async fn seek_time(
+    source: &LogSource,
+    time: DateTime<Utc>,
+) -> std::io::Result<Option<LogEntry>> {
+    let Some(block) = source.index.locate_time(time) else {
+        return Ok(None);
+    };
+    let format = source.log_format();
+
+    let mut file = File::open(&source.path).await?;
+    file.seek(SeekFrom::Start(block.byte_offset)).await?;
+    let mut reader = BufReader::new(file);
+    let mut assembler = EntryAssembler::starting_at(block.line_number, block.byte_offset);
+    let mut buffer = Vec::new();
+    let at_or_after = |entry: &LogEntry| entry.record.time.is_some_and(|t| t >= time);
+
+    loop {
+        buffer.clear();
+        if reader.read_until(b'\n', &mut buffer).await? == 0 {
+            break;
+        }
+        let line = buffer.strip_suffix(b"\n").unwrap_or(&buffer);
+        let raw_len = line.len();
+        let line = String::from_utf8_lossy(line).into_owned();
+        if let Some(entry) = assembler.push(format.as_ref(), line, raw_len) {
+            if at_or_after(&entry) {
+                return Ok(Some(entry));
+            }
+        }
+    }
+    Ok(assembler.finish().filter(at_or_after))
+}
+
+#[utoipa::path(
+    get,
+    path = "/sessions/{id}/seek",
+    params(
+        ("id" = String, Path, description = "Log session ID"),
+        ("ts" = String, Query, description = "Timestamp to jump to. Absolute timestamp or relative to the start (`+30s`) or end (`-15m`) of the log")
+    ),
+    responses(
+        (status = 200, description = "Position of the first entry at or after the timestamp. With several files, the earliest such entry of all files", body = SeekResponse),
+        (status = 400, description = "Invalid timestamp", body = ErrorResponse),
+        (status = 404, description = "Session ID not found or no entry at or after the timestamp")
+    ),
+    tag = "Log Streaming"
+)]
+pub async fn seek_handler(
+    Path(session_id): Path<String>,
+    Query(query): Query<SeekQuery>,
+) -> Response {
+    debug!("Seek request for session {}: {:?}", &session_id, &query.ts);
+
+    let Some(user_log) = get_user_log(&session_id) else {
+        return (StatusCode::NOT_FOUND, "Session ID not found").into_response();
+    };
+    let time = match resolve_time(&user_log, &query.ts) {
+        Ok(time) => time,
+        Err(e) => return bad_request(e),
+    };
+
+    // Earliest match of all files, ties go to the first file
+    let mut found: Option<(&LogSource, LogEntry)> = None;
+    for source in &user_log.sources {
+        match seek_time(source, time).await {
+            Ok(Some(entry)) => {
+                if found
+                    .as_ref()
+                    .is_none_or(|(_, first)| entry.record.time < first.record.time)
+                {
+                    found = Some((source, entry));
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                error!(
+                    "Failed to read log file for session {}: {:?}",
+                    &session_id, e
+                );
+                return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to read log file")
+                    .into_response();
+            }
+        }
+    }
+
+    match found {
+        Some((source, entry)) => Json(SeekResponse {
+            source: source.name.clone(),
+            line_number: entry.line_number,
+            byte_offset: entry.byte_offset,
+            ts: entry.record.timestamp,
+        })
+        .into_response(),
+        None => (StatusCode::NOT_FOUND, "No entry at or after the timestamp").into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1 @@
+mod seek_test;
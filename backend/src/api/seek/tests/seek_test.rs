@@ -0,0 +1,90 @@
+use super::super::*;
+use crate::parsing::entry::parse_entries;
+use crate::parsing::format::bracketed::BracketedFormat;
+use crate::parsing::line_index::LineIndexBuilder;
+use chrono::TimeZone;
+use std::sync::Arc;
+
+fn time(second: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 11, 23, 10, 0, second).unwrap()
+}
+
+fn user_log(sources: Vec<LogSource>) -> UserLog {
+    UserLog {
+        sources,
+        start_time: Some(time(0)),
+        stop_time: Some(time(59)),
+        summary: Default::default(),
+        uploaded_at: Utc::now(),
+        last_access: Utc::now(),
+    }
+}
+
+/// Write the entries with the given seconds to a file, indexed in blocks of two lines
+fn source(seconds: &[u32]) -> LogSource {
+    let text: String = seconds
+        .iter()
+        .map(|s| format!("[2025-11-23T10:00:{:02}.000Z] [INFO] [core] at {}\n", s, s))
+        .collect();
+    let path = std::env::temp_dir().join(format!("seek-test-{}.log", uuid::Uuid::new_v4()));
+    std::fs::write(&path, &text).unwrap();
+
+    let mut builder = LineIndexBuilder::new(2);
+    builder.push(text.as_bytes());
+    let mut index = builder.finish();
+    for entry in parse_entries(&BracketedFormat, &text) {
+        index.record_time(entry.line_number, entry.record.time.unwrap());
+    }
+    index.seal();
+
+    LogSource {
+        name: "app.log".into(),
+        path: path.to_string_lossy().to_string(),
+        file_name: Some("app.log".into()),
+        format: "bracketed".into(),
+        field_mapping: None,
+        index: Arc::new(index),
+        size: text.len() as u64,
+        sha256: String::new(),
+        summary: Default::default(),
+    }
+}
+
+#[test]
+fn test_resolve_time() {
+    let log = user_log(Vec::new());
+
+    assert_eq!(resolve_time(&log, "+30s"), Ok(time(30)));
+    assert_eq!(resolve_time(&log, "-9s"), Ok(time(50)));
+    assert_eq!(resolve_time(&log, "2025-11-23T10:00:07Z"), Ok(time(7)));
+    assert!(resolve_time(&log, "yesterday").is_err());
+}
+
+#[tokio::test]
+async fn test_seek_finds_the_first_entry_at_or_after_the_time() {
+    // four blocks, timestamps are not in order across them
+    let source = source(&[0, 1, 5, 2, 6, 3, 7, 8]);
+    let seek = |second: u32| {
+        let source = source.clone();
+        async move {
+            seek_time(&source, time(second))
+                .await
+                .unwrap()
+                .map(|entry| (entry.line_number, entry.record.message))
+        }
+    };
+
+    assert_eq!(seek(0).await, Some((1, "at 0".to_string())));
+    // the first entry in file order, not the closest timestamp
+    assert_eq!(seek(2).await, Some((3, "at 5".to_string())));
+    assert_eq!(seek(4).await, Some((3, "at 5".to_string())));
+    assert_eq!(seek(8).await, Some((8, "at 8".to_string())));
+    assert_eq!(seek(9).await, None);
+
+    // the offset points at the entry in the file
+    let entry = seek_time(&source, time(6)).await.unwrap().unwrap();
+    let text = std::fs::read_to_string(&source.path).unwrap();
+    assert!(text[entry.byte_offset as usize..].starts_with("[2025-11-23T10:00:06.000Z]"));
+
+    std::fs::remove_file(&source.path).unwrap();
+}
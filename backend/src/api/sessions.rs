@@ -0,0 +1,67 @@
+use crate::log_storage::eviction::env_or;
+use crate::log_storage::{all_user_logs, get_user_log};
+use crate::model::session_info::SessionInfo;
+
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use once_cell::sync::Lazy;
+use tracing::debug;
+
+/// Whether `GET /sessions` lists the stored sessions, on unless `SESSION_LISTING_ENABLED=false`.
+/// A session ID is all it takes to read a session, shared deployments turn the listing off.
+static SESSION_LISTING_ENABLED: Lazy<bool> =
+    Lazy::new(|| env_or("SESSION_LISTING_ENABLED", true).unwrap_or(true));
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/sessions", get(list_sessions))
+        .route("/sessions/{id}", get(get_session))
+}
+
+#[utoipa::path(
+    get,
+    path = "/sessions",
+    description = "Lists the IDs of all sessions, which gives access to every uploaded log. Servers \
+        shared by several users turn this off with `SESSION_LISTING_ENABLED=false`.",
+    responses(
+        (status = 200, description = "All stored sessions, most recently used first", body = Vec<SessionInfo>),
+        (status = 403, description = "Session listing is disabled by `SESSION_LISTING_ENABLED=false`")
+    ),
+    tag = "Sessions"
+)]
+pub async fn list_sessions() -> Response {
+    if !*SESSION_LISTING_ENABLED {
+        return (StatusCode::FORBIDDEN, "Session listing is disabled").into_response();
+    }
+    let mut sessions: Vec<SessionInfo> = all_user_logs()
+        .into_iter()
+        .map(|(session_id, log)| SessionInfo::new(session_id, log))
+        .collect();
+    sessions.sort_by_key(|session| std::cmp::Reverse(session.last_access));
+    Json(sessions).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/sessions/{id}",
+    params(
+        ("id" = String, Path, description = "Log session ID")
+    ),
+    responses(
+        (status = 200, description = "Metadata and summary of the session, counts as an access", body = SessionInfo),
+        (status = 404, description = "Session ID not found")
+    ),
+    tag = "Sessions"
+)]
+pub async fn get_session(Path(session_id): Path<String>) -> Response {
+    debug!("Session request for {}", &session_id);
+    match get_user_log(&session_id) {
+        Some(log) => Json(SessionInfo::new(session_id, log)).into_response(),
+        None => (StatusCode::NOT_FOUND, "Session ID not found").into_response(),
+    }
+}
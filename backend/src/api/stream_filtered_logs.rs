@@ -1,29 +1,42 @@
 // src/your_module.rs
 // Adjust module path / file name to match your project structure.
 
-use crate::log_storage::{get_user_log, remove_user_log};
+use crate::api::seek::{resolve_time, seek_time};
+use crate::api::{bad_request, filter_error_response};
+use crate::log_storage::{get_user_log, remove_user_log, LogSource};
 use crate::model::close_session_query::CloseSessionQuery;
-use crate::parsing::parser::LOG_REGEX;
+use crate::model::error_response::ErrorResponse;
+use crate::model::log_line::LogLine;
+use crate::parsing::context::{ContextEvent, ContextWindow};
+use crate::parsing::entry::{EntryAssembler, LogEntry};
+use crate::parsing::filter::{split_keywords, split_list, Filter, FilterSpec};
+use crate::parsing::format::LogFormat;
+use crate::parsing::keyword::MatchMode;
+use crate::parsing::level::Severity;
+use crate::parsing::merge::{KWayMerge, MergeCursor};
 
 use axum::{
-    extract::Query,
-    response::sse::{Event, Sse},
+    extract::{rejection::QueryRejection, Query},
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
-use futures::{stream, StreamExt};
+use chrono::{DateTime, Utc};
+use futures::{stream, stream::BoxStream, StreamExt};
 use serde::Deserialize;
 use serde_json::json;
 use std::convert::Infallible;
-use std::pin::Pin;
 use tokio::fs::File;
-use tokio::time::{interval, Duration};
-use tokio_util::codec::{FramedRead, LinesCodec};
+use tokio::io::{AsyncRead, AsyncSeekExt};
+use tokio_util::codec::{AnyDelimiterCodec, FramedRead};
 use tracing::{debug, info};
 
 use std::collections::VecDeque;
+use std::io::SeekFrom;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::Duration;
 
 pub fn router() -> Router {
     Router::new()
@@ -31,8 +44,89 @@ pub fn router() -> Router {
         .route("/close_session", post(close_session))
 }
 
-fn json_event(value: &serde_json::Value) -> Event {
-    Event::default().data(serde_json::to_string(value).unwrap())
+/// Reconnection time sent with the `done` event. EventSource reconnects whenever the server
+/// closes the stream, the client is expected to close it after `done` instead.
+const DONE_RETRY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// An event of the stream before it is encoded for SSE
+#[derive(Debug, Clone, PartialEq)]
+struct StreamEvent {
+    data: serde_json::Value,
+    id: Option<String>,
+    /// Reconnection time for EventSource
+    retry: Option<Duration>,
+}
+
+impl StreamEvent {
+    fn json(data: serde_json::Value) -> Self {
+        Self {
+            data,
+            id: None,
+            retry: None,
+        }
+    }
+
+    /// The last event of the stream, see [`DONE_RETRY`]
+    fn done(data: serde_json::Value) -> Self {
+        Self {
+            data,
+            id: None,
+            retry: Some(DONE_RETRY),
+        }
+    }
+
+    /// Entries are sent as [`LogLine`]s tagged with their source, the event ID is the cursor
+    /// after the entry
+    fn line(
+        entry: LogEntry,
+        source: &str,
+        context: bool,
+        filter: &Filter,
+        cursor: &MergeCursor,
+    ) -> Self {
+        let mut line = LogLine::new(entry, context, filter);
+        line.source = Some(source.to_string());
+        Self {
+            data: serde_json::to_value(line).unwrap(),
+            id: Some(cursor.to_string()),
+            retry: None,
+        }
+    }
+}
+
+impl From<StreamEvent> for Event {
+    fn from(event: StreamEvent) -> Self {
+        let mut sse = Event::default().data(serde_json::to_string(&event.data).unwrap());
+        if let Some(id) = event.id {
+            sse = sse.id(id);
+        }
+        if let Some(retry) = event.retry {
+            sse = sse.retry(retry);
+        }
+        sse
+    }
+}
+
+/// Cursor of the last entry the client received, sent back by EventSource on reconnect
+fn last_event_id(headers: &HeaderMap) -> Option<String> {
+    Some(
+        headers
+            .get("last-event-id")?
+            .to_str()
+            .ok()?
+            .trim()
+            .to_string(),
+    )
+}
+
+/// Cursor to resume after, the `cursor` parameter takes priority over the `Last-Event-ID` header
+fn resume_cursor(cursor: Option<&str>, headers: &HeaderMap) -> Option<String> {
+    cursor
+        .map(str::trim)
+        .filter(|cursor| !cursor.is_empty())
+        .map(str::to_string)
+        .or_else(|| last_event_id(headers))
+        .filter(|cursor| !cursor.is_empty())
 }
 
 #[derive(Deserialize)]
@@ -40,9 +134,80 @@ pub struct LogFilterQuery {
     pub session_id: String,
     pub domains: Option<String>,
     pub levels: Option<String>,
+    /// Optional minimum severity, e.g. `WARN` keeps WARN, ERROR and FATAL entries
+    pub min_level: Option<Severity>,
     pub keywords: Option<String>,
+    /// Comma-separated domains to hide, exclusions take priority over all other filters
+    pub exclude_domains: Option<String>,
+    /// Comma-separated log levels to hide
+    pub exclude_levels: Option<String>,
+    /// Comma-separated keywords to hide, matched according to `match_mode`. A single pattern in
+    /// regex mode.
+    pub exclude_keywords: Option<String>,
+    /// How keywords (also those in `query`) are matched, defaults to literal
+    pub match_mode: Option<MatchMode>,
+    /// Comma-separated conditions on additional fields, e.g. `status>=500,component=net`
+    pub fields: Option<String>,
+    /// Optional start of the time window, absolute or relative to the start of the log (`+30s`)
+    pub from: Option<String>,
+    /// Optional end of the time window, absolute or relative to the end of the log (`-15m`)
+    pub to: Option<String>,
+    /// Optional boolean query, e.g. `level:ERROR AND (domain:network OR domain:core)`
+    pub query: Option<String>,
     /// Optional context window size. If absent or zero -> no context expansion.
     pub context: Option<usize>,
+    /// Optional number of entries to include before each match, overrides `context`
+    pub before: Option<usize>,
+    /// Optional number of entries to include after each match, overrides `context`
+    pub after: Option<usize>,
+    /// Optional cursor of the last entry received, i.e. the ID of its event. The stream resumes
+    /// after it. Takes priority over the `Last-Event-ID` header.
+    pub cursor: Option<String>,
+    /// Optional timestamp to open the log at, earlier entries in the file are skipped
+    pub start_at: Option<String>,
+    /// Optional maximum number of matches to send, the `done` event carries the cursor of the
+    /// next page
+    pub limit: Option<usize>,
+    /// Optional comma-separated names of the session's files to include, all by default
+    pub sources: Option<String>,
+}
+
+impl LogFilterQuery {
+    /// Number of entries to include before and after each match
+    fn context_window(&self) -> (usize, usize) {
+        let context = self.context.unwrap_or(0);
+        (
+            self.before.unwrap_or(context),
+            self.after.unwrap_or(context),
+        )
+    }
+
+    /// Filter options of the query, lists are comma-separated. An empty list, e.g. `domains=`,
+    /// counts as not given.
+    fn filter_spec(&self) -> FilterSpec {
+        let non_empty = |list: Vec<String>| Some(list).filter(|list| !list.is_empty());
+        let list = |list: &Option<String>| list.as_deref().map(split_list).and_then(non_empty);
+        let match_mode = self.match_mode.unwrap_or_default();
+        let keywords = |list: &Option<String>| {
+            list.as_deref()
+                .map(|list| split_keywords(list, match_mode))
+                .and_then(non_empty)
+        };
+        FilterSpec {
+            domains: list(&self.domains),
+            levels: list(&self.levels),
+            min_level: self.min_level,
+            keywords: keywords(&self.keywords),
+            match_mode,
+            fields: list(&self.fields).unwrap_or_default(),
+            from: self.from.clone(),
+            to: self.to.clone(),
+            query: self.query.clone(),
+            exclude_domains: list(&self.exclude_domains).unwrap_or_default(),
+            exclude_levels: list(&self.exclude_levels).unwrap_or_default(),
+            exclude_keywords: keywords(&self.exclude_keywords).unwrap_or_default(),
+        }
+    }
 }
 
 #[utoipa::path(
@@ -51,16 +216,37 @@ pub struct LogFilterQuery {
     params(
         ("session_id" = String, Query, description = "Log session ID"),
         ("domains" = Option<String>, Query, description = "Comma-separated log domains to include"),
-        ("levels" = Option<String>, Query, description = "Comma-separated log levels to include"),
-        ("keywords" = Option<String>, Query, description = "Comma-separated keywords to include"),
-        ("context" = Option<usize>, Query, description = "Optional number of surrounding lines to include (±context)")
+        ("levels" = Option<String>, Query, description = "Comma-separated log levels to include, aliases like `warning` or `err` are normalized"),
+        ("min_level" = Option<Severity>, Query, description = "Only include entries at least this severe: TRACE < DEBUG < INFO < WARN < ERROR < FATAL"),
+        ("keywords" = Option<String>, Query, description = "Comma-separated keywords to include. With `match_mode=regex` the whole value is one pattern, use `|` for alternatives"),
+        ("exclude_domains" = Option<String>, Query, description = "Comma-separated log domains to hide, takes priority over all other filters"),
+        ("exclude_levels" = Option<String>, Query, description = "Comma-separated log levels to hide, takes priority over all other filters"),
+        ("exclude_keywords" = Option<String>, Query, description = "Comma-separated keywords to hide, takes priority over all other filters. Excluded entries are never sent as context either. With `match_mode=regex` the whole value is one pattern"),
+        ("match_mode" = Option<MatchMode>, Query, description = "How keywords are matched: literal (default), ignore_case, regex or word"),
+        ("fields" = Option<String>, Query, description = "Comma-separated conditions on additional fields, e.g. `status>=500,component=net`. Supported operators: =, !=, <, <=, >, >="),
+        ("from" = Option<String>, Query, description = "Only include entries at or after this time. Absolute timestamp or relative to the start of the log, e.g. `+30s`"),
+        ("to" = Option<String>, Query, description = "Only include entries at or before this time. Absolute timestamp or relative to the end of the log, e.g. `-15m`"),
+        ("query" = Option<String>, Query, description = "Boolean query, e.g. `level:ERROR AND (domain:network OR domain:core) AND NOT \"retrying\"`. Supports quoting, grouping, AND/OR/NOT and field prefixes (level:, domain:, message:, or any additional field)"),
+        ("context" = Option<usize>, Query, description = "Optional number of surrounding lines to include (±context)"),
+        ("before" = Option<usize>, Query, description = "Optional number of lines to include before each match like `grep -B`, overrides `context`"),
+        ("after" = Option<usize>, Query, description = "Optional number of lines to include after each match like `grep -A`, overrides `context`"),
+        ("start_at" = Option<String>, Query, description = "Open the log at the first entry at or after this time, entries before it in the file are skipped. Absolute timestamp or relative to the start (`+30s`) or end (`-15m`) of the log"),
+        ("cursor" = Option<String>, Query, description = "Resume after the entry with this cursor, i.e. the ID of the last event received. A cursor holds the byte offset of the last entry sent from each file, separated by commas. Takes priority over the `Last-Event-ID` header"),
+        ("limit" = Option<usize>, Query, description = "Maximum number of matches to send. Once reached, the `done` event carries the `cursor` of the next page"),
+        ("sources" = Option<String>, Query, description = "Comma-separated names of the session's files to include, as reported in the upload summary. All files by default"),
+        ("Last-Event-ID" = Option<String>, Header, description = "Set by EventSource on reconnect, the stream resumes after the entry with this cursor")
     ),
     responses(
         (
             status = 200,
-            description = "Stream of filtered log events",
+            description = "Stream of filtered log events. The entries of all files of the session are merged by timestamp. Each event is either an entry `{\"line\": ..., \"context\": bool, \"line_number\": ..., \"byte_offset\": ..., \"ts\": ..., \"level\": ..., \"domain\": ..., \"message\": ..., \"highlights\": [{\"start\": ..., \"end\": ...}], \"source\": ...}` with the cursor after it as event ID, or `{\"separator\": true}` between non-adjacent hunks. The stream is closed after a final `{\"done\": true, \"entries\": ..., \"matches\": ..., \"context_lines\": ..., \"hunks\": ..., \"cursor\": ...}` event, `cursor` is null once the end of the log is reached. It sets a long `retry` so EventSource does not reconnect, clients close the stream on `done`",
             content_type = "text/event-stream"
         ),
+        (
+            status = 400,
+            description = "Invalid query, unknown minimum log level, invalid keyword regex, excluded keyword regex, field filter, time window, start time, cursor or unknown source file. Query errors include the position they refer to",
+            body = ErrorResponse
+        ),
         (
             status = 404,
             description = "Session ID not found"
@@ -69,226 +255,423 @@ pub struct LogFilterQuery {
     tag = "Log Streaming"
 )]
 pub async fn stream_filtered_logs(
-    Query(query): Query<LogFilterQuery>,
-) -> Sse<Pin<Box<dyn futures::Stream<Item = Result<Event, Infallible>> + Send>>> {
+    headers: HeaderMap,
+    query: Result<Query<LogFilterQuery>, QueryRejection>,
+) -> Response {
+    // e.g. an unknown minimum log level
+    let query = match query {
+        Ok(Query(query)) => query,
+        Err(e) => return bad_request(e.body_text()),
+    };
+    let cursor = resume_cursor(query.cursor.as_deref(), &headers);
     debug!(
-        "Logfile filter request: Keywords {:?}, Domains {:?}, Levels {:?}, Context {:?}",
-        &query.keywords, &query.domains, &query.levels, &query.context
+        "Logfile filter request: Query {:?}, Keywords {:?} ({:?}), Exclude {:?} / {:?} / {:?}, Domains {:?}, Levels {:?}, Min level {:?}, Fields {:?}, From {:?}, To {:?}, Context {:?} (before {:?}, after {:?}), Start at {:?}, Cursor {:?}, Limit {:?}, Sources {:?}",
+        &query.query,
+        &query.keywords,
+        &query.match_mode,
+        &query.exclude_domains,
+        &query.exclude_levels,
+        &query.exclude_keywords,
+        &query.domains,
+        &query.levels,
+        &query.min_level,
+        &query.fields,
+        &query.from,
+        &query.to,
+        &query.context,
+        &query.before,
+        &query.after,
+        &query.start_at,
+        &cursor,
+        &query.limit,
+        &query.sources
     );
 
-    let file_path = match get_user_log(&query.session_id) {
-        Some(path) => path,
-        None => return Sse::new(stream::empty().boxed()),
+    let user_log = match get_user_log(&query.session_id) {
+        Some(log) => log,
+        None => return empty_stream(),
     };
 
-    let file = match File::open(file_path).await {
-        Ok(f) => f,
-        Err(err) => {
-            debug!(
-                "Failed to open log file for session {}: {}",
-                &query.session_id, err
-            );
-            return Sse::new(stream::empty().boxed());
-        }
+    // Compile the filter once, invalid regexes are reported instead of silently matching nothing
+    let filter = match Filter::compile(
+        &query.filter_spec(),
+        user_log.start_time,
+        user_log.stop_time,
+    ) {
+        Ok(filter) => filter,
+        Err(e) => return filter_error_response(e),
     };
 
-    // Parse filters once and reuse
-    let filter_levels = query.levels.clone().map(|s| {
-        s.split(',')
-            .map(|x| x.trim().to_string())
-            .collect::<Vec<_>>()
-    });
-    let filter_domains = query.domains.clone().map(|s| {
-        s.split(',')
-            .map(|x| x.trim().to_string())
-            .collect::<Vec<_>>()
-    });
-    let filter_keywords = query.keywords.clone().map(|s| {
-        s.split(',')
-            .map(|x| x.trim().to_string())
-            .collect::<Vec<_>>()
-    });
-
-    // Context window size (Option A: disabled unless specified)
-    let context = query.context.unwrap_or(0usize);
-
-    // Prev buffer holds up to `context` previous lines (most recent at back)
-    let prev_buffer: Arc<Mutex<VecDeque<String>>> =
-        Arc::new(Mutex::new(VecDeque::with_capacity(if context == 0 {
-            1
-        } else {
-            context
-        })));
-    // future_remaining indicates how many upcoming lines to treat as forward context
-    let future_remaining = Arc::new(Mutex::new(0usize));
-    // outgoing queue for events produced by processing a single incoming line (keeps order)
-    let out_queue: Arc<Mutex<VecDeque<Event>>> = Arc::new(Mutex::new(VecDeque::new()));
-
-    // Stream of raw lines (String)
-    let raw_lines = FramedRead::new(file, LinesCodec::new()).map(|res| res.unwrap_or_default());
-
-    // Main processing stream: we use filter_map that first consumes any events in out_queue,
-    // otherwise processes the next incoming line and possibly enqueues events.
-    let pb = prev_buffer.clone();
-    let fr = future_remaining.clone();
-    let oq = out_queue.clone();
-    let filter_levels_clone = filter_levels.clone();
-    let filter_domains_clone = filter_domains.clone();
-    let filter_keywords_clone = filter_keywords.clone();
-
-    let fl = raw_lines.filter_map(move |line| {
-        // clones for closure
-        let prev_buffer = pb.clone();
-        let future_remaining = fr.clone();
-        let out_queue = oq.clone();
-        let filter_levels = filter_levels_clone.clone();
-        let filter_domains = filter_domains_clone.clone();
-        let filter_keywords = filter_keywords_clone.clone();
-        async move {
-            // If there are already pending outgoing events, return the next one first.
-            if let Some(ev) = {
-                let mut q = out_queue.lock().await;
-                q.pop_front()
-            } {
-                return Some(ev);
+    let cursor = match cursor.as_deref() {
+        Some(text) => match MergeCursor::parse(text, user_log.sources.len()) {
+            Ok(cursor) => cursor,
+            Err(e) => return bad_request(e),
+        },
+        None => MergeCursor::start(user_log.sources.len()),
+    };
+
+    // Files to read, given by their position in the session
+    let selected: Vec<usize> = match query.sources.as_deref() {
+        Some(names) if !names.trim().is_empty() => {
+            let mut selected = Vec::new();
+            for name in split_list(names) {
+                match user_log.sources.iter().position(|s| s.name == name) {
+                    Some(position) => selected.push(position),
+                    None => return bad_request(format!("Unknown source file '{}'", name)),
+                }
             }
+            selected.sort_unstable();
+            selected.dedup();
+            selected
+        }
+        _ => (0..user_log.sources.len()).collect(),
+    };
+
+    let start_at = match query.start_at.as_deref().filter(|t| !t.trim().is_empty()) {
+        Some(text) => match resolve_time(&user_log, text) {
+            Ok(time) => Some(time),
+            Err(e) => return bad_request(e),
+        },
+        None => None,
+    };
 
-            // Determine whether this line matches the user-provided filters
-            let mut matched = false;
-            if let Some(caps) = LOG_REGEX.captures(&line) {
-                let level = caps.name("level").map(|m| m.as_str()).unwrap_or("");
-                let domain = caps.name("domain").map(|m| m.as_str()).unwrap_or("");
-                let message = caps.name("message").map(|m| m.as_str()).unwrap_or("");
-
-                let level_ok = filter_levels
-                    .as_ref()
-                    .map_or(true, |v| v.iter().any(|s| s == level));
-                let domain_ok = filter_domains
-                    .as_ref()
-                    .map_or(true, |v| v.iter().any(|s| s == domain));
-                let keyword_ok = filter_keywords
-                    .as_ref()
-                    .map_or(true, |v| v.iter().any(|kw| message.contains(kw)));
-                matched = level_ok && domain_ok && keyword_ok;
+    // Context window sizes, `before` and `after` default to the symmetric `context`
+    let (before, after) = query.context_window();
+
+    let mut readers = Vec::new();
+    for position in selected {
+        let source = &user_log.sources[position];
+        match SourceReader::open(source, position, &filter, &cursor, start_at, before, after).await
+        {
+            Ok(reader) => readers.push(reader),
+            Err(err) => {
+                debug!(
+                    "Failed to open log file {} for session {}: {}",
+                    source.name, &query.session_id, err
+                );
+                return empty_stream();
             }
+        }
+    }
+
+    let state = StreamState::new(
+        readers,
+        filter,
+        (before, after),
+        cursor,
+        query.limit.filter(|&limit| limit > 0),
+    );
 
-            // If context == 0: old behavior (emit only immediate matches)
-            if context == 0 {
-                if matched {
-                    let ev = json_event(&json!({ "line": line.clone(), "context": false }));
-                    return Some(ev);
-                } else {
-                    return None;
-                }
+    // Comments keep proxies from closing the connection during long scans without matches, they
+    // end together with the stream
+    Sse::new(
+        state
+            .events()
+            .map(|event| Ok::<_, Infallible>(event.into())),
+    )
+    .keep_alive(KeepAlive::default())
+    .into_response()
+}
+
+/// Reads the entries of one file of a session
+struct SourceReader<R = File> {
+    /// Name entries of the file are tagged with
+    name: String,
+    /// Position of the file in the session, i.e. in its cursor
+    position: usize,
+    lines: FramedRead<R, AnyDelimiterCodec>,
+    assembler: EntryAssembler,
+    format: Arc<dyn LogFormat>,
+    /// All entries of the file are read
+    exhausted: bool,
+}
+
+impl SourceReader<File> {
+    /// Open a file of a session. Without preceding context, blocks of the file before the time
+    /// window can be skipped. Without any context, so can the blocks before the cursor, entries
+    /// up to the cursor are still read to place separators but not sent again. Reading starts at
+    /// the latest of these positions and `start_at`, given as (line number, byte offset).
+    async fn open(
+        source: &LogSource,
+        position: usize,
+        filter: &Filter,
+        cursor: &MergeCursor,
+        start_at: Option<DateTime<Utc>>,
+        before: usize,
+        after: usize,
+    ) -> std::io::Result<Self> {
+        // Position of the first entry at or after `start_at`, the end of the file if there is
+        // none
+        let start = match start_at {
+            Some(time) => Some(match seek_time(source, time).await? {
+                Some(entry) => (entry.line_number, entry.byte_offset),
+                None => (source.index.total_lines + 1, source.index.total_bytes),
+            }),
+            None => None,
+        };
+
+        let index = &source.index;
+        let time_block = filter
+            .time_from()
+            .filter(|_| before == 0)
+            .and_then(|from| index.locate_time(from));
+        let cursor_block = cursor.0[position]
+            .filter(|_| before == 0 && after == 0)
+            .and_then(|cursor| index.locate_offset(cursor));
+
+        let mut file = File::open(&source.path).await?;
+        let mut assembler = EntryAssembler::new();
+        if let Some((line_number, byte_offset)) = time_block
+            .into_iter()
+            .chain(cursor_block)
+            .map(|block| (block.line_number, block.byte_offset))
+            .chain(start)
+            .max_by_key(|&(_, byte_offset)| byte_offset)
+        {
+            file.seek(SeekFrom::Start(byte_offset)).await?;
+            assembler = EntryAssembler::starting_at(line_number, byte_offset);
+        }
+
+        // Log format detected (or chosen) on upload
+        Ok(Self::new(
+            source.name.clone(),
+            position,
+            file,
+            assembler,
+            source.log_format(),
+        ))
+    }
+}
+
+impl<R: AsyncRead + Unpin> SourceReader<R> {
+    fn new(
+        name: String,
+        position: usize,
+        reader: R,
+        assembler: EntryAssembler,
+        format: Arc<dyn LogFormat>,
+    ) -> Self {
+        Self {
+            name,
+            position,
+            // Split on \n only, the assembler strips a trailing \r so byte offsets stay exact
+            lines: FramedRead::new(reader, AnyDelimiterCodec::new(b"\n".to_vec(), Vec::new())),
+            assembler,
+            format,
+            exhausted: false,
+        }
+    }
+
+    /// Read lines until the next entry is complete, `None` at the end of the file
+    async fn next_entry(&mut self) -> Option<LogEntry> {
+        while let Some(line) = self.lines.next().await {
+            // Continuation lines (e.g. stack traces) belong to the previous entry, an entry is
+            // only complete once the next one starts
+            let bytes = line.unwrap_or_default();
+            let line = String::from_utf8_lossy(&bytes).into_owned();
+            if let Some(entry) = self.assembler.push(self.format.as_ref(), line, bytes.len()) {
+                return Some(entry);
             }
+        }
+        self.exhausted = true;
+        self.assembler.finish()
+    }
+}
 
-            // context > 0: use prev_buffer + future_remaining + out_queue mechanism
-            // Acquire locks where necessary and perform logic
-            let mut events_to_return: Vec<Event> = Vec::new();
-
-            if matched {
-                // 1) Emit previous buffer lines (in order oldest -> newest) as context=true
-                {
-                    let mut pb_lock = prev_buffer.lock().await;
-                    while let Some(prev_line) = pb_lock.pop_front() {
-                        let ev = json_event(&json!({ "line": prev_line, "context": true }));
-                        events_to_return.push(ev);
-                    }
-                }
+/// Progress of a [`StreamState`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Phase {
+    /// Reading the file
+    Streaming,
+    /// The file is read, only pending events are left, the last one is `done`
+    Draining,
+}
 
-                // 2) Emit the matching line itself (context = false)
-                events_to_return.push(json_event(
-                    &json!({ "line": line.clone(), "context": false }),
-                ));
+/// Counts reported in the final `done` event
+#[derive(Debug, Default)]
+struct StreamCounts {
+    /// Entries read from the file
+    entries: usize,
+    /// Entries matching the filter
+    matches: usize,
+    /// Entries sent as context of a match
+    context: usize,
+    /// Separators sent between hunks
+    separators: usize,
+}
 
-                // 3) Set future_remaining = context (so next `context` input lines will be emitted as context)
-                {
-                    let mut fr_lock = future_remaining.lock().await;
-                    *fr_lock = context;
-                }
-            } else {
-                // Not matched
-                // If we have future_remaining > 0 => this line is forward context
-                let mut fr_lock = future_remaining.lock().await;
-                if *fr_lock > 0 {
-                    events_to_return.push(json_event(
-                        &json!({ "line": line.clone(), "context": true }),
-                    ));
-                    *fr_lock -= 1;
-                } else {
-                    // Otherwise push into prev_buffer (maintain its max size = context)
-                    let mut pb_lock = prev_buffer.lock().await;
-                    pb_lock.push_back(line.clone());
-                    while pb_lock.len() > context {
-                        pb_lock.pop_front();
-                    }
-                }
+impl StreamCounts {
+    /// Groups of adjacent entries sent
+    fn hunks(&self) -> usize {
+        if self.matches + self.context > 0 {
+            self.separators + 1
+        } else {
+            0
+        }
+    }
+}
+
+/// State machine turning the lines of a session's files into SSE events. The entries of the
+/// files are merged by timestamp, then each entry produces zero or more events: preceding
+/// context, the entry itself, following context and separators between hunks. At the end of the
+/// files all pending events are sent, followed by a `done` event.
+struct StreamState<R = File> {
+    readers: Vec<SourceReader<R>>,
+    merge: KWayMerge<LogEntry>,
+    filter: Filter,
+    /// Entries with the index of the reader they come from
+    window: ContextWindow<(usize, LogEntry)>,
+    /// Events produced but not yet sent, in order
+    pending: VecDeque<StreamEvent>,
+    counts: StreamCounts,
+    phase: Phase,
+    /// Entries the client already has, nothing covered by it is sent again
+    cursor: MergeCursor,
+    /// The client's cursor advanced by every entry sent
+    sent: MergeCursor,
+    /// The client's cursor advanced by every entry read, where the next page starts
+    read: MergeCursor,
+    /// Maximum number of matches to send
+    limit: Option<usize>,
+    /// A separator precedes the next line, sent only if that line is
+    separator_held: bool,
+}
+
+impl<R: AsyncRead + Unpin + Send + 'static> StreamState<R> {
+    /// Stream over the readers, which are merged by timestamp. The `context` window is given as
+    /// (before, after).
+    fn new(
+        readers: Vec<SourceReader<R>>,
+        filter: Filter,
+        (before, after): (usize, usize),
+        cursor: MergeCursor,
+        limit: Option<usize>,
+    ) -> Self {
+        Self {
+            merge: KWayMerge::new(readers.len()),
+            readers,
+            filter,
+            window: ContextWindow::new(before, after),
+            pending: VecDeque::new(),
+            counts: StreamCounts::default(),
+            phase: Phase::Streaming,
+            sent: cursor.clone(),
+            read: cursor.clone(),
+            cursor,
+            limit,
+            separator_held: false,
+        }
+    }
+
+    /// All events in order, the last one is `done`
+    fn events(self) -> BoxStream<'static, StreamEvent> {
+        stream::unfold(self, Self::next_event).boxed()
+    }
+
+    async fn next_event(mut self) -> Option<(StreamEvent, Self)> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some((event, self));
             }
 
-            // If we produced more than one event, store the rest in out_queue (so next poll returns them)
-            if events_to_return.len() > 1 {
-                let mut q = out_queue.lock().await;
-                // push all except first into queue
-                for ev in events_to_return.iter().skip(1) {
-                    q.push_back(ev.clone());
+            match self.phase {
+                Phase::Streaming => {
+                    // The earliest entry can only be taken once every file with entries left
+                    // has its next one in the merge
+                    if let Some(i) = (0..self.readers.len())
+                        .find(|&i| !self.readers[i].exhausted && self.merge.needs(i))
+                    {
+                        if let Some(entry) = self.readers[i].next_entry().await {
+                            self.merge.push(i, entry.record.time, entry);
+                        }
+                        continue;
+                    }
+                    match self.merge.pop() {
+                        Some((i, entry)) => self.process(i, entry),
+                        None => self.done(false),
+                    }
                 }
+                // Everything is sent, close the stream
+                Phase::Draining => return None,
             }
+        }
+    }
+
+    /// Filter a complete entry of the reader and queue the resulting events
+    fn process(&mut self, reader: usize, entry: LogEntry) {
+        let position = self.readers[reader].position;
+        let offset = entry.byte_offset;
+        let resumed = !self.cursor.covers(position, offset);
+        if resumed {
+            self.counts.entries += 1;
+            self.read.0[position] = Some(offset);
+        }
 
-            // Return first event if any
-            if let Some(first) = events_to_return.into_iter().next() {
-                Some(first)
-            } else {
-                None
-            }
+        // Excluded entries are dropped entirely, so they are never sent as context either
+        if self.filter.excludes(&entry.record) {
+            return;
+        }
+        let matched = self.filter.matches(&entry.record);
+        if matched && resumed {
+            self.counts.matches += 1;
         }
-    });
-
-    // Map Event to Result<Event, Infallible>
-    let file_emits = fl.map(|ev| Ok(ev) as Result<Event, Infallible>);
-
-    // Final flush: when the file stream ends, we must decide for remaining items in prev_buffer whether to emit them
-    let flush_buffer = {
-        let prev_buffer = prev_buffer.clone();
-        let future_remaining = future_remaining.clone();
-        stream::once(async move {
-            let mut out: Vec<Result<Event, Infallible>> = Vec::new();
-
-            // First, any pending future_remaining does not make sense at EOF for lines beyond EOF.
-            // Emit any prev_buffer lines that are within +/-context of any matched line that
-            // could still be in the previous processing (we don't have matched flags here anymore),
-            // but we can conservatively emit prev_buffer only if future_remaining > 0 (i.e., last match wanted forward context)
-            // or if prev_buffer contains any matches (shouldn't in our logic).
-            // Simpler and safe: emit remaining prev_buffer if future_remaining > 0 (they were requested as forward context),
-            // otherwise drop them.
-
-            let fr = { *future_remaining.lock().await };
-            let mut pb = prev_buffer.lock().await;
-
-            if fr > 0 {
-                // emit the remaining prev buffer lines as context=true
-                while let Some(item) = pb.pop_front() {
-                    let ev = json_event(&json!({ "line": item, "context": true }));
-                    out.push(Ok(ev));
+
+        for event in self.window.push((reader, entry), matched) {
+            match event {
+                ContextEvent::Separator => self.separator_held = true,
+                ContextEvent::Line {
+                    entry: (reader, entry),
+                    context,
+                } => {
+                    let separator = std::mem::take(&mut self.separator_held);
+                    let position = self.readers[reader].position;
+                    if self.cursor.covers(position, entry.byte_offset) {
+                        continue;
+                    }
+                    if separator {
+                        self.counts.separators += 1;
+                        self.pending
+                            .push_back(StreamEvent::json(json!({ "separator": true })));
+                    }
+                    if context {
+                        self.counts.context += 1;
+                    }
+                    self.sent.0[position] = Some(entry.byte_offset);
+                    self.pending.push_back(StreamEvent::line(
+                        entry,
+                        &self.readers[reader].name,
+                        context,
+                        &self.filter,
+                        &self.sent,
+                    ));
                 }
-            } else {
-                // nothing to emit at EOF in most cases; but (optional) we could emit trailing matches if needed.
             }
+        }
 
-            out.into_iter()
-        })
-        .flat_map(|iter| stream::iter(iter))
-    };
-
-    // Heartbeat stream to keep connections alive
-    let heartbeat = stream::unfold(interval(Duration::from_secs(15)), |mut intv| async move {
-        intv.tick().await;
-        Some((Ok(Event::default().comment("hb")), intv))
-    });
-
-    // Compose final stream
-    let final_stream = file_emits.chain(flush_buffer).chain(heartbeat).boxed();
+        // Stop at the page boundary, the next page resumes after this match
+        if matched && resumed && self.limit == Some(self.counts.matches) {
+            self.done(true);
+        }
+    }
+
+    /// Queue the final `done` event. If the end of the files was not reached, `cursor` is where
+    /// the next page starts.
+    fn done(&mut self, paged: bool) {
+        let counts = &self.counts;
+        self.pending.push_back(StreamEvent::done(json!({
+            "done": true,
+            "entries": counts.entries,
+            "matches": counts.matches,
+            "context_lines": counts.context,
+            "hunks": counts.hunks(),
+            "cursor": paged.then(|| self.read.to_string()),
+        })));
+        self.phase = Phase::Draining;
+    }
+}
 
-    Sse::new(final_stream)
+fn empty_stream() -> Response {
+    Sse::new(stream::empty::<Result<Event, Infallible>>()).into_response()
 }
 
 #[utoipa::path(
@@ -304,3 +687,6 @@ pub async fn close_session(Query(query): Query<CloseSessionQuery>) -> Json<&'sta
     info!("Removed log for session_id: {}", &query.session_id);
     Json("ok")
 }
+
+#[cfg(test)]
+mod tests;
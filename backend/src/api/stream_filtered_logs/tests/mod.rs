@@ -0,0 +1 @@
+mod stream_filtered_logs_test;
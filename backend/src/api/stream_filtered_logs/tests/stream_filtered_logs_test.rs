@@ -0,0 +1,295 @@
+use super::super::*;
+use crate::parsing::format::bracketed::BracketedFormat;
+use std::io::Cursor;
+
+type TestReader = SourceReader<Cursor<Vec<u8>>>;
+
+fn line(second: u32, level: &str, message: &str) -> String {
+    format!(
+        "[2025-11-23T10:00:{:02}.000Z] [{}] [core] {}",
+        second, level, message
+    )
+}
+
+fn reader(name: &str, position: usize, lines: &[String]) -> TestReader {
+    let text = lines
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect::<String>();
+    SourceReader::new(
+        name.to_string(),
+        position,
+        Cursor::new(text.into_bytes()),
+        EntryAssembler::new(),
+        Arc::new(BracketedFormat),
+    )
+}
+
+fn errors() -> Filter {
+    let spec = FilterSpec {
+        levels: Some(vec!["ERROR".to_string()]),
+        ..Default::default()
+    };
+    Filter::compile(&spec, None, None).unwrap()
+}
+
+async fn run(
+    readers: Vec<TestReader>,
+    context: (usize, usize),
+    cursor: MergeCursor,
+    limit: Option<usize>,
+) -> Vec<StreamEvent> {
+    StreamState::new(readers, errors(), context, cursor, limit)
+        .events()
+        .collect()
+        .await
+}
+
+/// Events written as the message of entries (`+` for context), `--` for separators and the
+/// `done` event as is
+fn describe(events: &[StreamEvent]) -> Vec<String> {
+    events
+        .iter()
+        .map(|event| {
+            let data = &event.data;
+            if let Some(message) = data["message"].as_str() {
+                let context = if data["context"] == true { "+" } else { "" };
+                format!("{}{}", context, message)
+            } else if data["separator"] == true {
+                "--".to_string()
+            } else {
+                data.to_string()
+            }
+        })
+        .collect()
+}
+
+fn done(
+    entries: usize,
+    matches: usize,
+    context: usize,
+    hunks: usize,
+    cursor: Option<&str>,
+) -> String {
+    json!({
+        "done": true,
+        "entries": entries,
+        "matches": matches,
+        "context_lines": context,
+        "hunks": hunks,
+        "cursor": cursor,
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn test_context_of_the_last_match_is_drained_at_eof() {
+    let lines = [
+        line(0, "INFO", "a"),
+        line(1, "INFO", "b"),
+        line(2, "ERROR", "c"),
+        line(3, "INFO", "d"),
+    ];
+
+    // the match is followed by less context than requested
+    let events = run(
+        vec![reader("app.log", 0, &lines)],
+        (1, 3),
+        MergeCursor::start(1),
+        None,
+    )
+    .await;
+    assert_eq!(
+        describe(&events),
+        vec!["+b", "c", "+d", &done(4, 1, 2, 1, None)]
+    );
+
+    // the match is the last line
+    let events = run(
+        vec![reader("app.log", 0, &lines[..3])],
+        (0, 2),
+        MergeCursor::start(1),
+        None,
+    )
+    .await;
+    assert_eq!(describe(&events), vec!["c", &done(3, 1, 0, 1, None)]);
+}
+
+#[tokio::test]
+async fn test_done_counts_hunks_and_separators() {
+    let lines = [
+        line(0, "ERROR", "a"),
+        line(1, "INFO", "b"),
+        line(2, "INFO", "c"),
+        line(3, "INFO", "d"),
+        line(4, "ERROR", "e"),
+    ];
+
+    let events = run(
+        vec![reader("app.log", 0, &lines)],
+        (1, 1),
+        MergeCursor::start(1),
+        None,
+    )
+    .await;
+    assert_eq!(
+        describe(&events),
+        vec!["a", "+b", "--", "+d", "e", &done(5, 2, 2, 2, None)]
+    );
+    assert_eq!(events.last().unwrap().retry, Some(DONE_RETRY));
+
+    let events = run(vec![], (1, 1), MergeCursor::start(0), None).await;
+    assert_eq!(describe(&events), vec![done(0, 0, 0, 0, None)]);
+}
+
+#[tokio::test]
+async fn test_sources_are_merged_by_time() {
+    let api = [line(0, "ERROR", "api 0"), line(2, "ERROR", "api 2")];
+    let db = [line(1, "ERROR", "db 1"), line(3, "ERROR", "db 3")];
+
+    let events = run(
+        vec![reader("api.log", 0, &api), reader("db.log", 1, &db)],
+        (0, 0),
+        MergeCursor::start(2),
+        None,
+    )
+    .await;
+    assert_eq!(
+        describe(&events),
+        vec!["api 0", "db 1", "api 2", "db 3", &done(4, 4, 0, 1, None)]
+    );
+    let sources: Vec<&str> = events[..4]
+        .iter()
+        .map(|event| event.data["source"].as_str().unwrap())
+        .collect();
+    assert_eq!(sources, ["api.log", "db.log", "api.log", "db.log"]);
+}
+
+#[test]
+fn test_cursor_parameter_takes_priority_over_last_event_id() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(resume_cursor(None, &headers), None);
+    assert_eq!(resume_cursor(Some("12"), &headers).as_deref(), Some("12"));
+
+    headers.insert("last-event-id", " 40,- ".parse().unwrap());
+    assert_eq!(resume_cursor(None, &headers).as_deref(), Some("40,-"));
+    assert_eq!(resume_cursor(Some(""), &headers).as_deref(), Some("40,-"));
+    assert_eq!(
+        resume_cursor(Some("12,7"), &headers).as_deref(),
+        Some("12,7")
+    );
+}
+
+#[tokio::test]
+async fn test_entries_covered_by_the_cursor_are_skipped() {
+    let lines = [
+        line(0, "ERROR", "a"),
+        line(1, "INFO", "b"),
+        line(2, "INFO", "c"),
+        line(3, "ERROR", "d"),
+        line(4, "ERROR", "e"),
+    ];
+    let all = run(
+        vec![reader("app.log", 0, &lines)],
+        (0, 1),
+        MergeCursor::start(1),
+        None,
+    )
+    .await;
+    assert_eq!(
+        describe(&all),
+        vec!["a", "+b", "--", "d", "e", &done(5, 3, 1, 2, None)]
+    );
+
+    // resuming after "b", the client still needs the separator before "d"
+    let resumed = run(
+        vec![reader("app.log", 0, &lines)],
+        (0, 1),
+        MergeCursor::parse(all[1].id.as_deref().unwrap(), 1).unwrap(),
+        None,
+    )
+    .await;
+    assert_eq!(
+        describe(&resumed),
+        vec!["--", "d", "e", &done(3, 2, 0, 2, None)]
+    );
+
+    // resuming after "d", the separator held before it is dropped along with it
+    let resumed = run(
+        vec![reader("app.log", 0, &lines)],
+        (0, 1),
+        MergeCursor::parse(all[3].id.as_deref().unwrap(), 1).unwrap(),
+        None,
+    )
+    .await;
+    assert_eq!(describe(&resumed), vec!["e", &done(1, 1, 0, 1, None)]);
+}
+
+#[tokio::test]
+async fn test_pages_resume_after_the_last_match() {
+    let lines = [
+        line(0, "ERROR", "a"),
+        line(1, "INFO", "b"),
+        line(2, "ERROR", "c"),
+        line(3, "INFO", "d"),
+    ];
+    let offset = |i: usize| lines[..i].iter().map(|l| l.len() + 1).sum::<usize>();
+
+    // the page ends at its last match, its following context belongs to the next page
+    let page = run(
+        vec![reader("app.log", 0, &lines)],
+        (0, 1),
+        MergeCursor::start(1),
+        Some(1),
+    )
+    .await;
+    let cursor = offset(0).to_string();
+    assert_eq!(describe(&page), vec!["a", &done(1, 1, 0, 1, Some(&cursor))]);
+
+    let page = run(
+        vec![reader("app.log", 0, &lines)],
+        (0, 1),
+        MergeCursor::parse(&cursor, 1).unwrap(),
+        Some(1),
+    )
+    .await;
+    let cursor = offset(2).to_string();
+    assert_eq!(
+        describe(&page),
+        vec!["+b", "c", &done(2, 1, 1, 1, Some(&cursor))]
+    );
+
+    let page = run(
+        vec![reader("app.log", 0, &lines)],
+        (0, 1),
+        MergeCursor::parse(&cursor, 1).unwrap(),
+        Some(1),
+    )
+    .await;
+    assert_eq!(describe(&page), vec!["+d", &done(1, 0, 1, 1, None)]);
+}
+
+#[tokio::test]
+async fn test_merged_pages_carry_a_cursor_per_source() {
+    let api = [line(0, "ERROR", "api 0"), line(2, "ERROR", "api 2")];
+    let db = [line(1, "ERROR", "db 1"), line(3, "ERROR", "db 3")];
+    let readers = || vec![reader("api.log", 0, &api), reader("db.log", 1, &db)];
+    let second = (api[0].len() + 1).to_string();
+
+    let page = run(readers(), (0, 0), MergeCursor::start(2), Some(3)).await;
+    let cursor = format!("{},0", second);
+    assert_eq!(
+        describe(&page),
+        vec!["api 0", "db 1", "api 2", &done(3, 3, 0, 1, Some(&cursor))]
+    );
+    assert_eq!(page[2].id.as_deref(), Some(cursor.as_str()));
+
+    let page = run(
+        readers(),
+        (0, 0),
+        MergeCursor::parse(&cursor, 2).unwrap(),
+        Some(3),
+    )
+    .await;
+    assert_eq!(describe(&page), vec!["db 3", &done(1, 1, 0, 1, None)]);
+}
@@ -1,14 +1,28 @@
 // local
-use crate::log_storage::save_user_log;
-use crate::model::upload_response::UploadResponse;
-use crate::parsing::parser::parse_log;
+use crate::log_storage::eviction::{enforce_limits, SESSION_LIMITS};
+use crate::log_storage::{
+    find_duplicate, save_shared_user_log, save_user_log, LogSource, UserLog, UPLOAD_DIR,
+};
+use crate::model::log_summary::LogSummary;
+use crate::model::upload_response::{FileFormat, UploadResponse};
+use crate::parsing::detect::{detect_format, score_format, SAMPLE_LINES};
+use crate::parsing::entry::{EntryAssembler, LogEntry};
+use crate::parsing::filter::Filter;
+use crate::parsing::format::ndjson::FieldMapping;
+use crate::parsing::format::{resolve_format, LogFormat};
+use crate::parsing::line_index::{LineIndex, LineIndexBuilder, BLOCK_LINES};
+use crate::parsing::parser::{merge_summaries, SummaryBuilder};
 // axum
 use axum::{
     extract::Multipart, http::StatusCode, response::IntoResponse, routing::post, Json, Router,
 };
 // use axum_extra::extract::Multipart;
 // tokio
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+// chrono
+use chrono::Utc;
+// sha2
+use sha2::{Digest, Sha256};
 // uuid
 use uuid::Uuid;
 // utoipa
@@ -16,7 +30,8 @@ use utoipa::ToSchema;
 // tracing
 use tracing::{debug, error, info, trace};
 // std
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Build the router
 pub fn router() -> Router {
@@ -26,23 +41,30 @@ pub fn router() -> Router {
 /// Dummy type to document multipart request body
 #[derive(ToSchema)]
 pub struct UploadFileBody {
-    /// The log file to upload
+    /// The log file to upload, repeat the field to upload several files into one session
     #[allow(dead_code)]
     #[schema(format = "binary")]
     pub file: Vec<u8>,
+    /// Optional name of the log format, detected automatically if omitted
+    #[allow(dead_code)]
+    pub format: Option<String>,
+    /// Optional field mapping (JSON) for the "ndjson" format
+    #[allow(dead_code)]
+    pub field_mapping: Option<FieldMapping>,
 }
 
-/// Upload and parse a log file
+/// Upload and parse one or more log files
 #[utoipa::path(
     post,
     path = "/upload",
     request_body(
         content = UploadFileBody,
-        description = "Log file to upload. Only the first file in the multipart request is processed. Maximum size: 1 GB",
+        description = "Log files to upload into one session, their entries are merged by timestamp when streamed. Maximum size: 1 GB. The log format of each file is detected from its first lines unless a `format` or `field_mapping` field is given, which then applies to all files",
     ),
     responses(
-        (status = 200, description = "Upload successful, returns session ID and log summary", body = UploadResponse),
-        (status = 400, description = "No file uploaded or invalid multipart request"),
+        (status = 200, description = "Upload successful, returns session ID, the log format of each file and log summary with statistics per file", body = UploadResponse),
+        (status = 400, description = "No file uploaded, unknown log format or invalid multipart request"),
+        (status = 507, description = "The files do not fit into the disk quota, even after evicting other sessions"),
         (status = 500, description = "Internal server error while creating directories, writing, or reading the file")
     ),
     tag = "Log Upload"
@@ -52,7 +74,7 @@ pub async fn upload_handler(mut multipart: Multipart) -> impl IntoResponse {
     let session_id = Uuid::new_v4().to_string();
 
     // Ensure upload directory exists
-    let upload_dir = PathBuf::from("./uploads");
+    let upload_dir = PathBuf::from(UPLOAD_DIR);
     if let Err(e) = tokio::fs::create_dir_all(&upload_dir).await {
         error!("Failed to create upload directory: {:?}", e);
         return (
@@ -62,65 +84,391 @@ pub async fn upload_handler(mut multipart: Multipart) -> impl IntoResponse {
             .into_response();
     }
 
-    let file_path = upload_dir.join(format!("{}.log", session_id));
-    let mut uploaded = false;
+    let mut files: Vec<UploadedFile> = Vec::new();
+    let mut format_override: Option<String> = None;
+    let mut field_mapping: Option<FieldMapping> = None;
+    let mut total_size: u64 = 0;
 
     while let Ok(Some(mut field)) = multipart.next_field().await {
         debug!("Processing uploaded field: {:?}", field.name());
+
+        match field.name() {
+            Some("format") => {
+                match field.text().await {
+                    Ok(text) if !text.trim().is_empty() => {
+                        format_override = Some(text.trim().into())
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("Failed to read format field: {:?}", e);
+                        remove_files(&files).await;
+                        return (StatusCode::BAD_REQUEST, "Invalid format field").into_response();
+                    }
+                }
+                continue;
+            }
+            Some("field_mapping") => {
+                let mapping = match field.text().await {
+                    Ok(text) => serde_json::from_str::<FieldMapping>(&text),
+                    Err(e) => {
+                        error!("Failed to read field_mapping field: {:?}", e);
+                        remove_files(&files).await;
+                        return (StatusCode::BAD_REQUEST, "Invalid field_mapping field")
+                            .into_response();
+                    }
+                };
+                match mapping {
+                    Ok(mapping) => field_mapping = Some(mapping),
+                    Err(e) => {
+                        remove_files(&files).await;
+                        return (
+                            StatusCode::BAD_REQUEST,
+                            format!("Invalid field_mapping field: {}", e),
+                        )
+                            .into_response();
+                    }
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        let file_path = upload_dir.join(format!("{}-{}.log", session_id, files.len()));
         let mut file = match tokio::fs::File::create(&file_path).await {
             Ok(f) => f,
             Err(e) => {
                 error!("Failed to create file: {:?}", e);
+                remove_files(&files).await;
                 return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create file")
                     .into_response();
             }
         };
+        let file_name = field.file_name().map(str::to_string);
+        // Line offsets are indexed and the content hashed while the file is written
+        let mut index_builder = LineIndexBuilder::new(BLOCK_LINES);
+        let mut hasher = Sha256::new();
 
         while let Ok(Some(chunk)) = field.chunk().await {
             trace!("Writing chunk of size: {}", chunk.len());
+            total_size += chunk.len() as u64;
+            if total_size > SESSION_LIMITS.max_disk_bytes {
+                drop(file);
+                let _ = tokio::fs::remove_file(&file_path).await;
+                remove_files(&files).await;
+                return (
+                    StatusCode::INSUFFICIENT_STORAGE,
+                    "Files exceed the disk quota",
+                )
+                    .into_response();
+            }
             if let Err(e) = file.write_all(&chunk).await {
                 error!("Failed writing chunk: {:?}", e);
+                drop(file);
+                let _ = tokio::fs::remove_file(&file_path).await;
+                remove_files(&files).await;
                 return (StatusCode::INTERNAL_SERVER_ERROR, "Failed writing file").into_response();
             }
+            index_builder.push(&chunk);
+            hasher.update(&chunk);
         }
 
         debug!("Finished writing file to {:?}", file_path);
-
-        uploaded = true;
-        break; // only handle first file
+        files.push(UploadedFile {
+            path: file_path,
+            file_name,
+            size: index_builder.size(),
+            index: index_builder.finish(),
+            sha256: format!("{:x}", hasher.finalize()),
+        });
     }
 
-    if !uploaded {
+    if files.is_empty() {
         return (StatusCode::BAD_REQUEST, "No file uploaded").into_response();
     }
 
-    // Save session info (store path)
-    save_user_log(&session_id, file_path.to_string_lossy().to_string());
-
-    // Read the log file to parse it
-    let log_text = match tokio::fs::read_to_string(&file_path).await {
-        Ok(text) => text,
-        Err(e) => {
-            error!("Failed to read log file for parsing: {:?}", e);
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to read log file for parsing",
-            )
-                .into_response();
+    // Source name and format of each file, plus the source of an identical upload to share
+    let mut planned: Vec<(String, Arc<dyn LogFormat>, Option<LogSource>)> = Vec::new();
+    let mut formats: Vec<FileFormat> = Vec::new();
+    for (n, file) in files.iter().enumerate() {
+        // Only the first lines are needed to detect the format
+        let sample = match read_sample(&file.path).await {
+            Ok(sample) => sample,
+            Err(e) => {
+                error!("Failed to read log file for parsing: {:?}", e);
+                remove_files(&files).await;
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to read log file for parsing",
+                )
+                    .into_response();
+            }
+        };
+        let sample: Vec<&str> = sample.iter().map(String::as_str).collect();
+
+        // Use the format requested by the client, otherwise detect it from the first lines
+        let (format, confidence) = if format_override.is_some() || field_mapping.is_some() {
+            match resolve_format(format_override.as_deref(), field_mapping.as_ref()) {
+                Ok(format) => {
+                    let confidence = score_format(format.as_ref(), &sample);
+                    (format, confidence)
+                }
+                Err(e) => {
+                    remove_files(&files).await;
+                    return (StatusCode::BAD_REQUEST, e).into_response();
+                }
+            }
+        } else {
+            let detection = detect_format(sample.iter().copied());
+            (detection.format, detection.confidence)
+        };
+        info!(
+            "Using log format {} for file {} of session {} (confidence {:.2})",
+            format.name(),
+            n,
+            session_id,
+            confidence
+        );
+
+        let names: Vec<&str> = planned.iter().map(|(name, _, _)| name.as_str()).collect();
+        let name = source_name(&names, file.file_name.as_deref(), n);
+        formats.push(FileFormat {
+            source: name.clone(),
+            format: format.name().to_string(),
+            confidence,
+        });
+        let existing = find_duplicate(&file.sha256, format.name(), field_mapping.as_ref());
+        planned.push((name, format, existing));
+    }
+
+    // Evict idle and least recently used sessions to make room for the new one, files shared
+    // with an identical upload take no additional space
+    let new_size: u64 = files
+        .iter()
+        .zip(&planned)
+        .filter(|(_, (_, _, existing))| existing.is_none())
+        .map(|(file, _)| file.size)
+        .sum();
+    if !enforce_limits(&SESSION_LIMITS, Some(new_size)) {
+        remove_files(&files).await;
+        return (
+            StatusCode::INSUFFICIENT_STORAGE,
+            "Files exceed the disk quota",
+        )
+            .into_response();
+    }
+
+    let mut sources: Vec<LogSource> = Vec::new();
+    // Paths of the files that were replaced by the file of an identical upload
+    let mut shared: Vec<PathBuf> = Vec::new();
+    for (n, (file, (name, format, existing))) in files.iter().zip(planned).enumerate() {
+        // An identical upload already has a file, summary and index, share them instead of
+        // parsing the content again
+        if let Some(existing) = existing {
+            info!(
+                "File {} of session {} shares the file {} of an identical upload",
+                n, session_id, existing.path
+            );
+            shared.push(file.path.clone());
+            sources.push(LogSource {
+                name,
+                file_name: file.file_name.clone(),
+                ..existing
+            });
+            continue;
         }
-    };
 
-    // Parse the log file to get the summary
-    let summary = parse_log(&log_text, None, None);
+        match parse_source(file, name, format.as_ref(), field_mapping.as_ref()).await {
+            Ok(source) => sources.push(source),
+            Err(e) => {
+                error!("Failed to read log file for parsing: {:?}", e);
+                remove_files(&files).await;
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to read log file for parsing",
+                )
+                    .into_response();
+            }
+        }
+    }
+
+    let summary = merge_summaries(sources.iter().map(|source| {
+        (
+            source.name.as_str(),
+            source.format.as_str(),
+            &source.summary,
+        )
+    }));
     info!(
         "Finished parsing log for session {}. Summary: {:?}",
         session_id, summary
     );
 
+    let log = UserLog {
+        sources,
+        start_time: summary.start_time,
+        stop_time: summary.stop_time,
+        summary: summary.clone(),
+        uploaded_at: Utc::now(),
+        last_access: Utc::now(),
+    };
+    if save_shared_user_log(&session_id, log.clone()) {
+        for path in &shared {
+            let _ = tokio::fs::remove_file(path).await;
+        }
+    } else {
+        // An identical upload was removed in the meantime, keep the new files after all
+        let mut sources = Vec::new();
+        for (file, source) in files.iter().zip(log.sources) {
+            if !shared.contains(&file.path) {
+                sources.push(source);
+                continue;
+            }
+            let format = source.log_format();
+            match parse_source(
+                file,
+                source.name,
+                format.as_ref(),
+                source.field_mapping.as_ref(),
+            )
+            .await
+            {
+                Ok(source) => sources.push(source),
+                Err(e) => {
+                    error!("Failed to read log file for parsing: {:?}", e);
+                    remove_files(&files).await;
+                    return (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "Failed to read log file for parsing",
+                    )
+                        .into_response();
+                }
+            }
+        }
+        save_user_log(&session_id, UserLog { sources, ..log });
+    }
+
     // Respond with session ID and the actual summary
     Json(UploadResponse {
         session_id,
+        format: formats[0].format.clone(),
+        confidence: formats[0].confidence,
+        files: formats,
         summary,
     })
     .into_response()
 }
+
+/// A file written during an upload, not parsed yet
+struct UploadedFile {
+    path: PathBuf,
+    file_name: Option<String>,
+    /// Line offsets, without timestamps until the file is parsed
+    index: LineIndex,
+    size: u64,
+    sha256: String,
+}
+
+/// Remove the files written during a failed upload
+async fn remove_files(files: &[UploadedFile]) {
+    for file in files {
+        let _ = tokio::fs::remove_file(&file.path).await;
+    }
+}
+
+/// Name entries of an uploaded file are tagged with: its file name, or its position if it has
+/// none, made unique within the session
+fn source_name(taken: &[&str], file_name: Option<&str>, n: usize) -> String {
+    let base = file_name
+        .filter(|name| !name.is_empty())
+        .map_or_else(|| format!("file-{}", n + 1), str::to_string);
+    let mut name = base.clone();
+    let mut suffix = 2;
+    while taken.contains(&name.as_str()) {
+        name = format!("{} ({})", base, suffix);
+        suffix += 1;
+    }
+    name
+}
+
+/// Parse an uploaded file to get its summary and the timestamps of its index
+async fn parse_source(
+    file: &UploadedFile,
+    name: String,
+    format: &dyn LogFormat,
+    field_mapping: Option<&FieldMapping>,
+) -> std::io::Result<LogSource> {
+    let mut index = file.index.clone();
+    let summary = summarize_file(&file.path, format, &mut index).await?;
+    Ok(LogSource {
+        name,
+        path: file.path.to_string_lossy().to_string(),
+        file_name: file.file_name.clone(),
+        format: format.name().to_string(),
+        field_mapping: field_mapping.cloned(),
+        index: Arc::new(index),
+        size: file.size,
+        sha256: file.sha256.clone(),
+        summary,
+    })
+}
+
+/// Read the first lines of a file to detect its format, invalid UTF-8 is replaced like when
+/// the file is parsed
+async fn read_sample(path: &Path) -> std::io::Result<Vec<String>> {
+    let mut reader = BufReader::new(tokio::fs::File::open(path).await?);
+    let mut sample = Vec::new();
+    let mut buffer = Vec::new();
+    while sample.len() < SAMPLE_LINES {
+        buffer.clear();
+        if reader.read_until(b'\n', &mut buffer).await? == 0 {
+            break;
+        }
+        let line = buffer.strip_suffix(b"\n").unwrap_or(&buffer);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        sample.push(String::from_utf8_lossy(line).into_owned());
+    }
+    Ok(sample)
+}
+
+/// Summarize a file entry by entry without reading it at once, the timestamp of every entry is
+/// recorded in the index
+async fn summarize_file(
+    path: &Path,
+    format: &dyn LogFormat,
+    index: &mut LineIndex,
+) -> std::io::Result<LogSummary> {
+    let mut reader = BufReader::new(tokio::fs::File::open(path).await?);
+    let filter = Filter::default();
+    let mut summary = SummaryBuilder::new(&filter);
+    let mut assembler = EntryAssembler::new();
+    let mut buffer = Vec::new();
+
+    loop {
+        buffer.clear();
+        if reader.read_until(b'\n', &mut buffer).await? == 0 {
+            break;
+        }
+        let line = buffer.strip_suffix(b"\n").unwrap_or(&buffer);
+        let raw_len = line.len();
+        let line = String::from_utf8_lossy(line).into_owned();
+        if let Some(entry) = assembler.push(format, line, raw_len) {
+            add_entry(&mut summary, index, &entry);
+        }
+    }
+    if let Some(entry) = assembler.finish() {
+        add_entry(&mut summary, index, &entry);
+    }
+    index.seal();
+
+    Ok(summary.finish())
+}
+
+fn add_entry(summary: &mut SummaryBuilder, index: &mut LineIndex, entry: &LogEntry) {
+    summary.add(&entry.record);
+    if let Some(time) = entry.record.time {
+        index.record_time(entry.line_number, time);
+    }
+}
+
+#[cfg(test)]
+mod tests;
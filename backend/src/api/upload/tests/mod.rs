@@ -0,0 +1 @@
+mod upload_test;
@@ -0,0 +1,25 @@
+use super::super::*;
+
+#[tokio::test]
+async fn test_sample_with_invalid_utf8_is_read_lossily() {
+    let path = std::env::temp_dir().join(format!("upload-test-{}.log", Uuid::new_v4()));
+    let mut content = b"[2025-11-23 10:00:00] [INFO] [core] Starting process\r\n".to_vec();
+    content.extend_from_slice(b"[2025-11-23 10:00:01] [ERROR] [core] Bad byte \xff\xfe\n");
+    content.extend_from_slice(b"[2025-11-23 10:00:02] [INFO] [core] Done");
+    std::fs::write(&path, content).unwrap();
+
+    let sample = read_sample(&path).await.unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        sample,
+        vec![
+            "[2025-11-23 10:00:00] [INFO] [core] Starting process",
+            "[2025-11-23 10:00:01] [ERROR] [core] Bad byte \u{fffd}\u{fffd}",
+            "[2025-11-23 10:00:02] [INFO] [core] Done",
+        ]
+    );
+    let detection = detect_format(sample.iter().map(String::as_str));
+    assert_eq!(detection.format.name(), "bracketed");
+    assert_eq!(detection.confidence, 1.0);
+}
@@ -1,14 +1,42 @@
 // local
-use crate::api::{filter, upload};
-use crate::model::filter::{FilterRequest, FilteredLogResponse};
-use crate::model::log_summary::LogSummary;
+use crate::api::{filter, seek, sessions, upload};
+use crate::model::error_response::ErrorResponse;
+use crate::model::filter::{FilterRequest, FilteredLogResponse, Hunk};
+use crate::model::log_line::{LogLine, MatchSpan};
+use crate::model::log_summary::{FileSummary, LogSummary};
+use crate::model::seek_response::SeekResponse;
+use crate::model::session_info::{SessionFile, SessionInfo};
+use crate::parsing::format::ndjson::FieldMapping;
+use crate::parsing::keyword::MatchMode;
+use crate::parsing::level::Severity;
 // utoipa
 use utoipa::OpenApi;
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(upload::upload_handler, filter::filter_handler),
-    components(schemas(LogSummary, FilterRequest, FilteredLogResponse)),
+    paths(
+        upload::upload_handler,
+        filter::filter_handler,
+        seek::seek_handler,
+        sessions::list_sessions,
+        sessions::get_session
+    ),
+    components(schemas(
+        LogSummary,
+        FileSummary,
+        SeekResponse,
+        SessionInfo,
+        SessionFile,
+        FilterRequest,
+        FilteredLogResponse,
+        Hunk,
+        LogLine,
+        MatchSpan,
+        FieldMapping,
+        ErrorResponse,
+        MatchMode,
+        Severity
+    )),
     info(title = "Logfile Processor API", version = "1.0")
 )]
 pub struct ApiDoc;
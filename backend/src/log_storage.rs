@@ -1,40 +1,203 @@
+// local
+use crate::log_storage::memory::MemoryStore;
+use crate::model::log_summary::LogSummary;
+use crate::parsing::format::ndjson::FieldMapping;
+use crate::parsing::format::{default_format, resolve_format, LogFormat};
+use crate::parsing::line_index::LineIndex;
+// chrono
+use chrono::{DateTime, Utc};
+// serde
+use serde::{Deserialize, Serialize};
 // tracing
-use tracing::info;
+use tracing::{info, warn};
 // once_cell
-use once_cell::sync::Lazy;
+use once_cell::sync::OnceCell;
 // std
-use std::collections::HashMap;
-use std::sync::Mutex;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 
-static LOG_STORAGE: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+pub mod eviction;
+pub mod file_index;
+pub mod manifest;
+pub mod memory;
 
-/// Save a user's log path
-pub fn save_user_log(session_id: &str, file_path: String) {
-    LOG_STORAGE
-        .lock()
-        .unwrap()
-        .insert(session_id.to_string(), file_path.clone());
+/// Directory uploaded files are stored in
+pub const UPLOAD_DIR: &str = "./uploads";
+
+/// Name of the manifest the sessions are persisted to, inside [`UPLOAD_DIR`]
+pub const MANIFEST_FILE: &str = "sessions.json";
+
+/// One uploaded file of a session
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LogSource {
+    /// Name of the file unique within its session, entries are tagged with it
+    pub name: String,
+    /// Path of the uploaded file on disk, identical uploads share one
+    pub path: String,
+    /// Name of the file as uploaded by the client
+    pub file_name: Option<String>,
+    /// Name of the log format used to parse the file
+    pub format: String,
+    /// Custom field mapping for the NDJSON format
+    pub field_mapping: Option<FieldMapping>,
+    /// Line offsets and timestamps built on upload for random access
+    pub index: Arc<LineIndex>,
+    /// Size of the file in bytes
+    pub size: u64,
+    /// SHA-256 of the file content, hex encoded
+    pub sha256: String,
+    /// Summary of the file computed on upload
+    pub summary: LogSummary,
+}
+
+impl LogSource {
+    /// Format the file is parsed with, detected (or chosen) on upload
+    pub fn log_format(&self) -> Arc<dyn LogFormat> {
+        resolve_format(Some(&self.format), self.field_mapping.as_ref())
+            .unwrap_or_else(|_| default_format())
+    }
+}
+
+/// A log uploaded by a user, consisting of one or more files
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UserLog {
+    /// Uploaded files, their entries are merged by timestamp
+    pub sources: Vec<LogSource>,
+    /// Earliest parsed timestamp in all files, used to resolve relative times
+    pub start_time: Option<DateTime<Utc>>,
+    /// Latest parsed timestamp in all files, used to resolve relative times
+    pub stop_time: Option<DateTime<Utc>>,
+    /// Summary of all files with statistics per file
+    pub summary: LogSummary,
+    /// When the files were uploaded
+    pub uploaded_at: DateTime<Utc>,
+    /// When the session was last used, idle sessions are evicted
+    pub last_access: DateTime<Utc>,
+}
+
+/// Storage mapping session IDs to uploaded logs
+pub trait SessionStore: Send + Sync {
+    /// Add or replace a session
+    fn save(&self, session_id: &str, log: UserLog);
+
+    /// Look up a session
+    fn get(&self, session_id: &str) -> Option<UserLog>;
+
+    /// Record an access to a session, only kept in memory until the next change is saved or
+    /// the store is flushed
+    fn touch(&self, session_id: &str, time: DateTime<Utc>);
+
+    /// Persist the accesses recorded since the last change
+    fn flush(&self);
+
+    /// All sessions
+    fn list(&self) -> Vec<(String, UserLog)>;
+
+    /// Remove a session, returns its log if it existed
+    fn remove(&self, session_id: &str) -> Option<UserLog>;
+
+    /// A stored file with the same content, format and field mapping
+    fn find_file(
+        &self,
+        sha256: &str,
+        format: &str,
+        field_mapping: Option<&FieldMapping>,
+    ) -> Option<LogSource>;
+
+    /// Number of sessions referring to the file at `path`
+    fn file_references(&self, path: &str) -> usize;
+
+    /// Number of sessions stored
+    fn count(&self) -> usize;
+}
+
+static SESSION_STORE: OnceCell<Box<dyn SessionStore>> = OnceCell::new();
+
+/// Held while files are removed or new sessions start sharing them, so a file is never removed
+/// while another session is about to use it
+static SHARED_FILES: Mutex<()> = Mutex::new(());
+
+/// Set the store used for all sessions. Must be called before the first session is accessed,
+/// otherwise sessions are kept in memory only.
+pub fn init_session_store(store: impl SessionStore + 'static) {
+    if SESSION_STORE.set(Box::new(store)).is_err() {
+        warn!("Session store is already initialized");
+    }
+}
+
+fn store() -> &'static dyn SessionStore {
+    SESSION_STORE
+        .get_or_init(|| Box::new(MemoryStore::default()))
+        .as_ref()
+}
+
+/// Save a user's log
+pub fn save_user_log(session_id: &str, log: UserLog) {
+    store().save(session_id, log);
     info!("Saved log for session_id: {}", session_id);
-    info!(
-        "Currently {} log files stored",
-        LOG_STORAGE.lock().unwrap().len()
-    );
+    info!("Currently {} log files stored", store().count());
+}
+
+/// Retrieve a user's log, this counts as an access
+pub fn get_user_log(session_id: &str) -> Option<UserLog> {
+    store().touch(session_id, Utc::now());
+    store().get(session_id)
 }
 
-/// Retrieve a user's log path
-pub fn get_user_log(session_id: &str) -> Option<String> {
-    LOG_STORAGE.lock().unwrap().get(session_id).cloned()
+/// A stored file with the same content, format and field mapping, its file, summary and index
+/// can be reused instead of parsing the content again
+pub fn find_duplicate(
+    sha256: &str,
+    format: &str,
+    field_mapping: Option<&FieldMapping>,
+) -> Option<LogSource> {
+    store().find_file(sha256, format, field_mapping)
 }
 
-/// Remove a user's log
+/// Save a user's log whose files may belong to other sessions as well. Returns false, without
+/// saving, if one of the files has been removed in the meantime.
+pub fn save_shared_user_log(session_id: &str, log: UserLog) -> bool {
+    let _guard = SHARED_FILES.lock().unwrap();
+    if !log
+        .sources
+        .iter()
+        .all(|source| Path::new(&source.path).is_file())
+    {
+        return false;
+    }
+    save_user_log(session_id, log);
+    true
+}
+
+/// Persist the recorded accesses, so idle sessions are still recognized after a restart
+pub fn flush_user_logs() {
+    store().flush();
+}
+
+/// All stored logs with their session IDs, without counting as an access
+pub fn all_user_logs() -> Vec<(String, UserLog)> {
+    store().list()
+}
+
+/// Remove a user's log, each file is removed along with the last session referring to it
 pub fn remove_user_log(session_id: &str) {
-    if let Some(path) = LOG_STORAGE.lock().unwrap().remove(session_id) {
-        // optionally remove the file from disk
-        let _ = std::fs::remove_file(path);
+    let _guard = SHARED_FILES.lock().unwrap();
+    if let Some(log) = store().remove(session_id) {
+        for source in log.sources {
+            let references = store().file_references(&source.path);
+            if references == 0 {
+                let _ = std::fs::remove_file(source.path);
+            } else {
+                info!(
+                    "Keeping file {} of session {}, {} other sessions refer to it",
+                    source.path, session_id, references
+                );
+            }
+        }
     }
     info!("Removed log for session_id: {}", session_id);
-    info!(
-        "Currently {} log files stored",
-        LOG_STORAGE.lock().unwrap().len()
-    );
+    info!("Currently {} log files stored", store().count());
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,191 @@
+// local
+use crate::log_storage::{all_user_logs, flush_user_logs, remove_user_log};
+// chrono
+use chrono::{DateTime, Duration, Utc};
+// tracing
+use tracing::{info, warn};
+// once_cell
+use once_cell::sync::Lazy;
+// std
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Limits enforced on the stored sessions, read from the environment once
+pub static SESSION_LIMITS: Lazy<SessionLimits> = Lazy::new(SessionLimits::from_env);
+
+/// Limits on the stored sessions and their files
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionLimits {
+    /// Sessions not accessed for this long are removed
+    pub idle_ttl: Duration,
+    /// Maximum size of all uploaded files together in bytes
+    pub max_disk_bytes: u64,
+    /// Maximum number of sessions
+    pub max_sessions: usize,
+    /// Time between two garbage collection runs
+    pub gc_interval: Duration,
+}
+
+impl Default for SessionLimits {
+    fn default() -> Self {
+        Self {
+            idle_ttl: Duration::hours(24),
+            max_disk_bytes: 20 * 1024 * 1024 * 1024,
+            max_sessions: 100,
+            gc_interval: Duration::minutes(1),
+        }
+    }
+}
+
+impl SessionLimits {
+    /// Defaults overridden by `SESSION_IDLE_TTL_SECS`, `SESSION_MAX_DISK_BYTES`,
+    /// `SESSION_MAX_COUNT` and `SESSION_GC_INTERVAL_SECS`
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            idle_ttl: env_or("SESSION_IDLE_TTL_SECS", defaults.idle_ttl.num_seconds())
+                .map_or(defaults.idle_ttl, Duration::seconds),
+            max_disk_bytes: env_or("SESSION_MAX_DISK_BYTES", defaults.max_disk_bytes)
+                .unwrap_or(defaults.max_disk_bytes),
+            max_sessions: env_or("SESSION_MAX_COUNT", defaults.max_sessions)
+                .unwrap_or(defaults.max_sessions),
+            gc_interval: env_or(
+                "SESSION_GC_INTERVAL_SECS",
+                defaults.gc_interval.num_seconds(),
+            )
+            .map_or(defaults.gc_interval, Duration::seconds),
+        }
+    }
+}
+
+/// Value of an environment variable, `default` if unset. `None` if it is invalid.
+pub(crate) fn env_or<T: FromStr>(name: &str, default: T) -> Option<T> {
+    match std::env::var(name) {
+        Ok(value) => {
+            let parsed = value.trim().parse().ok();
+            if parsed.is_none() {
+                warn!("Ignoring invalid value '{}' of {}", value, name);
+            }
+            parsed
+        }
+        Err(_) => Some(default),
+    }
+}
+
+/// What eviction needs to know about a session
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionUsage {
+    pub session_id: String,
+    pub last_access: DateTime<Utc>,
+    /// Path and size in bytes of each of the session's files, identical uploads share one
+    pub files: Vec<(String, u64)>,
+}
+
+/// Why a session is evicted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EvictionReason {
+    /// Not accessed for longer than the idle TTL
+    Idle,
+    /// Least recently used while the disk usage or session count exceeds its limit
+    OverQuota,
+}
+
+/// Sessions to evict so the limits hold, plus room for an upload of `incoming` bytes if given.
+/// Idle sessions go first, then the least recently used ones. A shared file only frees its
+/// space once all sessions referring to it are evicted. `None` if the upload can never fit, in
+/// which case nothing needs to be evicted for it.
+pub fn plan_evictions(
+    sessions: &[SessionUsage],
+    limits: &SessionLimits,
+    now: DateTime<Utc>,
+    incoming: Option<u64>,
+) -> Option<Vec<(String, EvictionReason)>> {
+    let (extra_bytes, extra_sessions) = incoming.map_or((0, 0), |size| (size, 1));
+    if extra_bytes > limits.max_disk_bytes || extra_sessions > limits.max_sessions {
+        return None;
+    }
+
+    let mut by_access: Vec<&SessionUsage> = sessions.iter().collect();
+    by_access.sort_by_key(|session| session.last_access);
+
+    let mut references: HashMap<&str, usize> = HashMap::new();
+    let mut bytes = extra_bytes;
+    for (path, size) in sessions.iter().flat_map(|session| &session.files) {
+        let count = references.entry(path).or_default();
+        if *count == 0 {
+            bytes += size;
+        }
+        *count += 1;
+    }
+    let mut count = sessions.len() + extra_sessions;
+    let mut evictions = Vec::new();
+    for session in by_access {
+        let reason = if now - session.last_access > limits.idle_ttl {
+            EvictionReason::Idle
+        } else if bytes > limits.max_disk_bytes || count > limits.max_sessions {
+            EvictionReason::OverQuota
+        } else {
+            break;
+        };
+        for (path, size) in &session.files {
+            let remaining = references.get_mut(path.as_str()).unwrap();
+            *remaining -= 1;
+            if *remaining == 0 {
+                bytes -= size;
+            }
+        }
+        count -= 1;
+        evictions.push((session.session_id.clone(), reason));
+    }
+    Some(evictions)
+}
+
+/// Remove sessions so the limits hold, plus room for an upload of `incoming` bytes if given.
+/// Returns false if the upload can never fit.
+pub fn enforce_limits(limits: &SessionLimits, incoming: Option<u64>) -> bool {
+    let sessions: Vec<SessionUsage> = all_user_logs()
+        .into_iter()
+        .map(|(session_id, log)| SessionUsage {
+            session_id,
+            last_access: log.last_access,
+            files: log
+                .sources
+                .iter()
+                .map(|source| (source.path.clone(), source.size))
+                .collect(),
+        })
+        .collect();
+
+    let Some(evictions) = plan_evictions(&sessions, limits, Utc::now(), incoming) else {
+        return false;
+    };
+    for (session_id, reason) in evictions {
+        if let Some(session) = sessions.iter().find(|s| s.session_id == session_id) {
+            info!(
+                "Evicting session {} ({:?}): {} bytes in {} files, last accessed {}",
+                session_id,
+                reason,
+                session.files.iter().map(|(_, size)| size).sum::<u64>(),
+                session.files.len(),
+                session.last_access
+            );
+        }
+        remove_user_log(&session_id);
+    }
+    true
+}
+
+/// Enforce the limits and persist the recorded accesses every `gc_interval`, runs forever
+pub async fn collect_garbage(limits: &SessionLimits) {
+    let period = limits
+        .gc_interval
+        .to_std()
+        .unwrap_or(std::time::Duration::from_secs(60))
+        .max(std::time::Duration::from_secs(1));
+    let mut interval = tokio::time::interval(period);
+    loop {
+        interval.tick().await;
+        enforce_limits(limits, None);
+        flush_user_logs();
+    }
+}
@@ -0,0 +1,89 @@
+// local
+use crate::log_storage::{LogSource, UserLog};
+use crate::parsing::format::ndjson::FieldMapping;
+// std
+use std::collections::{HashMap, HashSet};
+
+/// Lookups of the files stored sessions refer to, kept by the stores next to the sessions so
+/// identical uploads are found without going through every session
+#[derive(Debug, Default)]
+pub struct FileIndex {
+    /// Number of sessions referring to each file and the source it was first saved as
+    files: HashMap<String, (usize, LogSource)>,
+    /// Paths of the files by the SHA-256 of their content
+    by_sha256: HashMap<String, HashSet<String>>,
+}
+
+impl FileIndex {
+    /// Index the files of all sessions
+    pub fn new<'a>(logs: impl IntoIterator<Item = &'a UserLog>) -> Self {
+        let mut index = Self::default();
+        for log in logs {
+            index.add(log);
+        }
+        index
+    }
+
+    /// Count the references of a saved session to its files
+    pub fn add(&mut self, log: &UserLog) {
+        for source in &log.sources {
+            let (references, _) = self
+                .files
+                .entry(source.path.clone())
+                .or_insert_with(|| (0, source.clone()));
+            *references += 1;
+            if !source.sha256.is_empty() {
+                self.by_sha256
+                    .entry(source.sha256.clone())
+                    .or_default()
+                    .insert(source.path.clone());
+            }
+        }
+    }
+
+    /// Drop the references of a removed session to its files
+    pub fn remove(&mut self, log: &UserLog) {
+        for source in &log.sources {
+            let Some((references, _)) = self.files.get_mut(&source.path) else {
+                continue;
+            };
+            *references -= 1;
+            if *references > 0 {
+                continue;
+            }
+            if let Some((_, first)) = self.files.remove(&source.path) {
+                if let Some(paths) = self.by_sha256.get_mut(&first.sha256) {
+                    paths.remove(&source.path);
+                    if paths.is_empty() {
+                        self.by_sha256.remove(&first.sha256);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Number of sessions referring to the file at `path`
+    pub fn references(&self, path: &str) -> usize {
+        self.files
+            .get(path)
+            .map_or(0, |(references, _)| *references)
+    }
+
+    /// A file with the content hash, parsed with the same format and field mapping
+    pub fn find(
+        &self,
+        sha256: &str,
+        format: &str,
+        field_mapping: Option<&FieldMapping>,
+    ) -> Option<LogSource> {
+        self.by_sha256
+            .get(sha256)?
+            .iter()
+            .filter_map(|path| self.files.get(path))
+            .map(|(_, source)| source)
+            .find(|source| {
+                source.format == format && source.field_mapping.as_ref() == field_mapping
+            })
+            .cloned()
+    }
+}
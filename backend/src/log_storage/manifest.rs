@@ -0,0 +1,287 @@
+// local
+use crate::log_storage::file_index::FileIndex;
+use crate::log_storage::{LogSource, SessionStore, UserLog};
+use crate::model::log_summary::LogSummary;
+use crate::parsing::format::ndjson::FieldMapping;
+use crate::parsing::line_index::LineIndex;
+use crate::parsing::parser::merge_summaries;
+// chrono
+use chrono::{DateTime, Utc};
+// serde
+use serde::Deserialize;
+// tracing
+use tracing::{error, info, warn};
+// std
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// A session as stored in the manifest, earlier versions only had a single file per session
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredLog {
+    Current(UserLog),
+    SingleFile(SingleFileLog),
+}
+
+/// Layout of a session before it could consist of several files
+#[derive(Deserialize)]
+struct SingleFileLog {
+    path: String,
+    #[serde(default)]
+    file_name: Option<String>,
+    format: String,
+    field_mapping: Option<FieldMapping>,
+    start_time: Option<DateTime<Utc>>,
+    stop_time: Option<DateTime<Utc>>,
+    index: Arc<LineIndex>,
+    #[serde(default)]
+    size: u64,
+    #[serde(default)]
+    sha256: String,
+    #[serde(default)]
+    summary: LogSummary,
+    #[serde(default = "Utc::now")]
+    uploaded_at: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    last_access: DateTime<Utc>,
+}
+
+impl From<StoredLog> for UserLog {
+    fn from(stored: StoredLog) -> Self {
+        let log = match stored {
+            StoredLog::Current(log) => return log,
+            StoredLog::SingleFile(log) => log,
+        };
+        let source = LogSource {
+            name: log.file_name.clone().unwrap_or_else(|| "log".into()),
+            path: log.path,
+            file_name: log.file_name,
+            format: log.format,
+            field_mapping: log.field_mapping,
+            index: log.index,
+            size: log.size,
+            sha256: log.sha256,
+            summary: log.summary,
+        };
+        UserLog {
+            summary: merge_summaries([(
+                source.name.as_str(),
+                source.format.as_str(),
+                &source.summary,
+            )]),
+            sources: vec![source],
+            start_time: log.start_time,
+            stop_time: log.stop_time,
+            uploaded_at: log.uploaded_at,
+            last_access: log.last_access,
+        }
+    }
+}
+
+/// Sessions kept in memory and written to a JSON manifest on every change, so they survive a
+/// restart of the server
+#[derive(Debug)]
+pub struct ManifestStore {
+    path: PathBuf,
+    sessions: Mutex<HashMap<String, UserLog>>,
+    /// Files of the sessions, only changed while the sessions are locked
+    files: Mutex<FileIndex>,
+    /// Bumped with every change while the sessions are locked, orders the snapshots written
+    version: AtomicU64,
+    /// Version of the manifest on disk, held while writing so writes never interleave
+    written: Mutex<u64>,
+    /// Whether sessions were accessed since the last snapshot
+    touched: AtomicBool,
+}
+
+/// Copy of the sessions after a change, written without holding the sessions lock
+type Snapshot = (u64, HashMap<String, UserLog>);
+
+impl ManifestStore {
+    /// Load the sessions from the manifest at `path`, if it exists, and reconcile them with the
+    /// uploaded files in `upload_dir`: sessions with a file that is gone are dropped and `.log`
+    /// files no session refers to are removed.
+    pub fn open(path: impl Into<PathBuf>, upload_dir: &Path) -> io::Result<Self> {
+        let path = path.into();
+        let mut sessions: HashMap<String, UserLog> = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice::<HashMap<String, StoredLog>>(&bytes)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+                .into_iter()
+                .map(|(session_id, stored)| (session_id, stored.into()))
+                .collect(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e),
+        };
+
+        sessions.retain(|session_id, log| {
+            log.sources
+                .iter_mut()
+                .all(|source| match std::fs::metadata(&source.path) {
+                    Ok(metadata) if metadata.is_file() => {
+                        source.size = metadata.len();
+                        true
+                    }
+                    _ => {
+                        warn!(
+                            "Dropping session {}, its file {} is gone",
+                            session_id, source.path
+                        );
+                        false
+                    }
+                })
+        });
+
+        let referenced: HashSet<OsString> = sessions
+            .values()
+            .flat_map(|log| &log.sources)
+            .filter_map(|source| Path::new(&source.path).file_name().map(OsString::from))
+            .collect();
+        if upload_dir.is_dir() {
+            for entry in std::fs::read_dir(upload_dir)? {
+                let file = entry?.path();
+                let orphaned = file.extension().is_some_and(|ext| ext == "log")
+                    && file
+                        .file_name()
+                        .is_some_and(|name| !referenced.contains(name));
+                if orphaned {
+                    info!("Removing file {:?} of unknown session", file);
+                    std::fs::remove_file(&file)?;
+                }
+            }
+        }
+
+        info!(
+            "Restored {} sessions from {:?}",
+            sessions.len(),
+            path.as_path()
+        );
+        let store = Self {
+            path,
+            files: Mutex::new(FileIndex::new(sessions.values())),
+            sessions: Mutex::new(sessions),
+            version: AtomicU64::new(0),
+            written: Mutex::new(0),
+            touched: AtomicBool::new(false),
+        };
+        store.write(&store.sessions.lock().unwrap())?;
+        Ok(store)
+    }
+
+    /// Replace the manifest, the new one is written next to it first so a crash never leaves a
+    /// truncated manifest behind
+    fn write(&self, sessions: &HashMap<String, UserLog>) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_vec(sessions)?;
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, &self.path)
+    }
+
+    /// Take a snapshot of the sessions after a change, must be called while they are locked
+    fn snapshot(&self, sessions: &HashMap<String, UserLog>) -> Snapshot {
+        let version = self.version.fetch_add(1, Ordering::Relaxed) + 1;
+        self.touched.store(false, Ordering::Relaxed);
+        (version, sessions.clone())
+    }
+
+    /// Persist a snapshot unless a later one has been written already. Called after the
+    /// sessions are unlocked so lookups do not wait for the disk, failures only cost durability.
+    fn persist(&self, (version, sessions): Snapshot) {
+        let mut written = self.written.lock().unwrap();
+        if *written >= version {
+            return;
+        }
+        match self.write(&sessions) {
+            Ok(()) => *written = version,
+            Err(e) => error!("Failed to write session manifest {:?}: {:?}", self.path, e),
+        }
+    }
+}
+
+impl SessionStore for ManifestStore {
+    fn save(&self, session_id: &str, log: UserLog) {
+        let snapshot = {
+            let mut sessions = self.sessions.lock().unwrap();
+            let mut files = self.files.lock().unwrap();
+            files.add(&log);
+            if let Some(replaced) = sessions.insert(session_id.to_string(), log) {
+                files.remove(&replaced);
+            }
+            self.snapshot(&sessions)
+        };
+        self.persist(snapshot);
+    }
+
+    fn get(&self, session_id: &str) -> Option<UserLog> {
+        self.sessions.lock().unwrap().get(session_id).cloned()
+    }
+
+    fn touch(&self, session_id: &str, time: DateTime<Utc>) {
+        if let Some(log) = self.sessions.lock().unwrap().get_mut(session_id) {
+            log.last_access = time;
+            self.touched.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn flush(&self) {
+        let snapshot = {
+            let sessions = self.sessions.lock().unwrap();
+            if !self.touched.load(Ordering::Relaxed) {
+                return;
+            }
+            self.snapshot(&sessions)
+        };
+        self.persist(snapshot);
+    }
+
+    fn list(&self) -> Vec<(String, UserLog)> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(session_id, log)| (session_id.clone(), log.clone()))
+            .collect()
+    }
+
+    fn remove(&self, session_id: &str) -> Option<UserLog> {
+        let (log, snapshot) = {
+            let mut sessions = self.sessions.lock().unwrap();
+            let log = sessions.remove(session_id);
+            if let Some(log) = &log {
+                self.files.lock().unwrap().remove(log);
+            }
+            let snapshot = log.is_some().then(|| self.snapshot(&sessions));
+            (log, snapshot)
+        };
+        if let Some(snapshot) = snapshot {
+            self.persist(snapshot);
+        }
+        log
+    }
+
+    fn find_file(
+        &self,
+        sha256: &str,
+        format: &str,
+        field_mapping: Option<&FieldMapping>,
+    ) -> Option<LogSource> {
+        self.files
+            .lock()
+            .unwrap()
+            .find(sha256, format, field_mapping)
+    }
+
+    fn file_references(&self, path: &str) -> usize {
+        self.files.lock().unwrap().references(path)
+    }
+
+    fn count(&self) -> usize {
+        self.sessions.lock().unwrap().len()
+    }
+}
@@ -0,0 +1,76 @@
+// local
+use crate::log_storage::file_index::FileIndex;
+use crate::log_storage::{LogSource, SessionStore, UserLog};
+use crate::parsing::format::ndjson::FieldMapping;
+// chrono
+use chrono::{DateTime, Utc};
+// std
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Sessions kept in memory only, they are lost when the server restarts
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    sessions: Mutex<HashMap<String, UserLog>>,
+    /// Files of the sessions, only changed while the sessions are locked
+    files: Mutex<FileIndex>,
+}
+
+impl SessionStore for MemoryStore {
+    fn save(&self, session_id: &str, log: UserLog) {
+        let mut sessions = self.sessions.lock().unwrap();
+        let mut files = self.files.lock().unwrap();
+        files.add(&log);
+        if let Some(replaced) = sessions.insert(session_id.to_string(), log) {
+            files.remove(&replaced);
+        }
+    }
+
+    fn get(&self, session_id: &str) -> Option<UserLog> {
+        self.sessions.lock().unwrap().get(session_id).cloned()
+    }
+
+    fn touch(&self, session_id: &str, time: DateTime<Utc>) {
+        if let Some(log) = self.sessions.lock().unwrap().get_mut(session_id) {
+            log.last_access = time;
+        }
+    }
+
+    fn flush(&self) {}
+
+    fn list(&self) -> Vec<(String, UserLog)> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(session_id, log)| (session_id.clone(), log.clone()))
+            .collect()
+    }
+
+    fn remove(&self, session_id: &str) -> Option<UserLog> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let log = sessions.remove(session_id)?;
+        self.files.lock().unwrap().remove(&log);
+        Some(log)
+    }
+
+    fn find_file(
+        &self,
+        sha256: &str,
+        format: &str,
+        field_mapping: Option<&FieldMapping>,
+    ) -> Option<LogSource> {
+        self.files
+            .lock()
+            .unwrap()
+            .find(sha256, format, field_mapping)
+    }
+
+    fn file_references(&self, path: &str) -> usize {
+        self.files.lock().unwrap().references(path)
+    }
+
+    fn count(&self) -> usize {
+        self.sessions.lock().unwrap().len()
+    }
+}
@@ -0,0 +1,135 @@
+use super::super::eviction::*;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 11, 23, 12, 0, 0).unwrap()
+}
+
+fn session(session_id: &str, minutes_ago: i64, size: u64) -> SessionUsage {
+    shared(session_id, session_id, minutes_ago, size)
+}
+
+fn shared(session_id: &str, path: &str, minutes_ago: i64, size: u64) -> SessionUsage {
+    SessionUsage {
+        session_id: session_id.into(),
+        last_access: now() - Duration::minutes(minutes_ago),
+        files: vec![(path.into(), size)],
+    }
+}
+
+fn limits(max_disk_bytes: u64, max_sessions: usize) -> SessionLimits {
+    SessionLimits {
+        idle_ttl: Duration::hours(1),
+        max_disk_bytes,
+        max_sessions,
+        gc_interval: Duration::minutes(1),
+    }
+}
+
+fn evicted(plan: Option<Vec<(String, EvictionReason)>>) -> Vec<(String, EvictionReason)> {
+    plan.expect("upload should fit")
+}
+
+#[test]
+fn test_idle_sessions_are_evicted() {
+    let sessions = [
+        session("a", 30, 10),
+        session("b", 90, 10),
+        session("c", 61, 10),
+    ];
+
+    assert_eq!(
+        evicted(plan_evictions(&sessions, &limits(1000, 10), now(), None)),
+        vec![
+            ("b".to_string(), EvictionReason::Idle),
+            ("c".to_string(), EvictionReason::Idle)
+        ]
+    );
+}
+
+#[test]
+fn test_least_recently_used_are_evicted_over_quota() {
+    let sessions = [
+        session("a", 5, 40),
+        session("b", 20, 40),
+        session("c", 10, 40),
+    ];
+
+    // within limits, nothing to do
+    assert!(evicted(plan_evictions(&sessions, &limits(120, 3), now(), None)).is_empty());
+
+    // too many bytes
+    assert_eq!(
+        evicted(plan_evictions(&sessions, &limits(100, 3), now(), None)),
+        vec![("b".to_string(), EvictionReason::OverQuota)]
+    );
+
+    // too many sessions
+    assert_eq!(
+        evicted(plan_evictions(&sessions, &limits(1000, 1), now(), None)),
+        vec![
+            ("b".to_string(), EvictionReason::OverQuota),
+            ("c".to_string(), EvictionReason::OverQuota)
+        ]
+    );
+}
+
+#[test]
+fn test_room_is_made_for_uploads() {
+    let sessions = [session("a", 5, 40), session("b", 20, 40)];
+
+    assert_eq!(
+        evicted(plan_evictions(&sessions, &limits(100, 10), now(), Some(30))),
+        vec![("b".to_string(), EvictionReason::OverQuota)]
+    );
+    // the new session counts as well
+    assert_eq!(
+        evicted(plan_evictions(&sessions, &limits(1000, 2), now(), Some(1))),
+        vec![("b".to_string(), EvictionReason::OverQuota)]
+    );
+    // larger than the whole quota, nothing is evicted for it
+    assert!(plan_evictions(&sessions, &limits(100, 10), now(), Some(101)).is_none());
+    assert!(plan_evictions(&sessions, &limits(100, 0), now(), Some(1)).is_none());
+}
+
+#[test]
+fn test_shared_files_count_once() {
+    let sessions = [
+        shared("a", "blob", 5, 60),
+        shared("b", "blob", 30, 60),
+        session("c", 20, 30),
+    ];
+
+    // 90 bytes on disk, not 150
+    assert!(evicted(plan_evictions(&sessions, &limits(90, 10), now(), None)).is_empty());
+
+    // evicting "b" frees nothing while "a" still uses the file
+    assert_eq!(
+        evicted(plan_evictions(&sessions, &limits(50, 10), now(), None)),
+        vec![
+            ("b".to_string(), EvictionReason::OverQuota),
+            ("c".to_string(), EvictionReason::OverQuota),
+            ("a".to_string(), EvictionReason::OverQuota)
+        ]
+    );
+    assert_eq!(
+        evicted(plan_evictions(&sessions, &limits(60, 10), now(), None)),
+        vec![
+            ("b".to_string(), EvictionReason::OverQuota),
+            ("c".to_string(), EvictionReason::OverQuota)
+        ]
+    );
+}
+
+#[test]
+fn test_all_files_of_a_session_count() {
+    let mut both = session("both", 30, 40);
+    both.files.push(("other".into(), 40));
+    let sessions = [both, session("new", 5, 40)];
+
+    assert!(evicted(plan_evictions(&sessions, &limits(120, 10), now(), None)).is_empty());
+    assert_eq!(
+        evicted(plan_evictions(&sessions, &limits(100, 10), now(), None)),
+        vec![("both".to_string(), EvictionReason::OverQuota)]
+    );
+}
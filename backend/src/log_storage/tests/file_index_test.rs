@@ -0,0 +1,67 @@
+use super::super::memory::MemoryStore;
+use super::super::*;
+use crate::parsing::line_index::LineIndexBuilder;
+
+fn user_log(path: &str, sha256: &str, format: &str) -> UserLog {
+    let mut index = LineIndexBuilder::new(2);
+    index.push(b"a\nb\nc\n");
+    UserLog {
+        sources: vec![LogSource {
+            name: path.into(),
+            path: path.into(),
+            file_name: None,
+            format: format.into(),
+            field_mapping: None,
+            index: Arc::new(index.finish()),
+            size: 6,
+            sha256: sha256.into(),
+            summary: Default::default(),
+        }],
+        start_time: None,
+        stop_time: None,
+        summary: Default::default(),
+        uploaded_at: Utc::now(),
+        last_access: Utc::now(),
+    }
+}
+
+#[test]
+fn test_files_are_found_by_content_format_and_mapping() {
+    let store = MemoryStore::default();
+    store.save("a", user_log("a.log", "abc", "bracketed"));
+    store.save("b", user_log("b.log", "abc", "logfmt"));
+    store.save("c", user_log("c.log", "", "bracketed"));
+
+    let found = |sha256, format| store.find_file(sha256, format, None).map(|log| log.path);
+    assert_eq!(found("abc", "bracketed"), Some("a.log".into()));
+    assert_eq!(found("abc", "logfmt"), Some("b.log".into()));
+    assert_eq!(found("abc", "ndjson"), None);
+    // files without a hash are never shared
+    assert_eq!(found("", "bracketed"), None);
+    let mapping = FieldMapping::default();
+    assert!(store
+        .find_file("abc", "bracketed", Some(&mapping))
+        .is_none());
+}
+
+#[test]
+fn test_references_follow_saved_and_removed_sessions() {
+    let store = MemoryStore::default();
+    store.save("a", user_log("shared.log", "abc", "bracketed"));
+    store.save("b", user_log("shared.log", "abc", "bracketed"));
+    assert_eq!(store.file_references("shared.log"), 2);
+
+    // replacing a session moves its reference to the new file
+    store.save("b", user_log("other.log", "def", "bracketed"));
+    assert_eq!(store.file_references("shared.log"), 1);
+    assert_eq!(store.file_references("other.log"), 1);
+
+    store.remove("a");
+    assert_eq!(store.file_references("shared.log"), 0);
+    assert!(store.find_file("abc", "bracketed", None).is_none());
+    assert!(store.find_file("def", "bracketed", None).is_some());
+
+    // removing an unknown session changes nothing
+    assert!(store.remove("a").is_none());
+    assert_eq!(store.file_references("other.log"), 1);
+}
@@ -0,0 +1,67 @@
+use super::super::*;
+use crate::parsing::line_index::LineIndexBuilder;
+use std::path::{Path, PathBuf};
+
+// These tests use the global session store, every test uses its own session IDs and files
+
+fn temp_file() -> PathBuf {
+    let path = std::env::temp_dir().join(format!("log-storage-test-{}.log", uuid::Uuid::new_v4()));
+    std::fs::write(&path, "a\nb\nc\n").unwrap();
+    path
+}
+
+fn session_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+fn user_log(path: &Path) -> UserLog {
+    let mut index = LineIndexBuilder::new(2);
+    index.push(b"a\nb\nc\n");
+    UserLog {
+        sources: vec![LogSource {
+            name: "shared.log".into(),
+            path: path.to_string_lossy().to_string(),
+            file_name: Some("shared.log".into()),
+            format: "bracketed".into(),
+            field_mapping: None,
+            index: Arc::new(index.finish()),
+            size: 6,
+            sha256: "abc".into(),
+            summary: Default::default(),
+        }],
+        start_time: None,
+        stop_time: None,
+        summary: Default::default(),
+        uploaded_at: Utc::now(),
+        last_access: Utc::now(),
+    }
+}
+
+#[test]
+fn test_shared_file_is_removed_with_the_last_session() {
+    let file = temp_file();
+    let (first, second) = (session_id(), session_id());
+    save_user_log(&first, user_log(&file));
+    assert!(save_shared_user_log(&second, user_log(&file)));
+
+    remove_user_log(&first);
+    assert!(get_user_log(&first).is_none());
+    assert!(file.is_file());
+    assert!(get_user_log(&second).is_some());
+
+    remove_user_log(&second);
+    assert!(get_user_log(&second).is_none());
+    assert!(!file.exists());
+}
+
+#[test]
+fn test_sharing_a_removed_file_fails() {
+    let file = temp_file();
+    let (first, second) = (session_id(), session_id());
+    save_user_log(&first, user_log(&file));
+    remove_user_log(&first);
+    assert!(!file.exists());
+
+    assert!(!save_shared_user_log(&second, user_log(&file)));
+    assert!(get_user_log(&second).is_none());
+}
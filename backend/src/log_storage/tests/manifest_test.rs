@@ -0,0 +1,208 @@
+use super::super::manifest::ManifestStore;
+use super::super::*;
+use crate::parsing::line_index::LineIndexBuilder;
+use std::path::{Path, PathBuf};
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("manifest-test-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn source(path: &Path) -> LogSource {
+    let mut index = LineIndexBuilder::new(2);
+    index.push(b"a\nb\nc\n");
+    LogSource {
+        name: "kept.log".into(),
+        path: path.to_string_lossy().to_string(),
+        file_name: Some("kept.log".into()),
+        format: "bracketed".into(),
+        field_mapping: None,
+        index: Arc::new(index.finish()),
+        size: 6,
+        sha256: String::new(),
+        summary: Default::default(),
+    }
+}
+
+fn user_log(paths: &[&Path]) -> UserLog {
+    UserLog {
+        sources: paths.iter().map(|path| source(path)).collect(),
+        start_time: None,
+        stop_time: None,
+        summary: Default::default(),
+        uploaded_at: Utc::now(),
+        last_access: Utc::now(),
+    }
+}
+
+#[test]
+fn test_sessions_survive_reopening() {
+    let dir = temp_dir();
+    let manifest = dir.join(MANIFEST_FILE);
+    let file = dir.join("kept.log");
+
+    let store = ManifestStore::open(&manifest, &dir).unwrap();
+    std::fs::write(&file, "a\nb\nc\n").unwrap();
+    store.save("kept", user_log(&[&file]));
+    store.save("removed", user_log(&[&file]));
+    assert!(store.remove("removed").is_some());
+    drop(store);
+
+    let store = ManifestStore::open(&manifest, &dir).unwrap();
+    assert_eq!(store.count(), 1);
+    let log = store.get("kept").unwrap();
+    assert_eq!(log.sources[0].format, "bracketed");
+    assert_eq!(*log.sources[0].index, *source(&file).index);
+    assert!(store.get("removed").is_none());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_reopening_reconciles_with_files() {
+    let dir = temp_dir();
+    let manifest = dir.join(MANIFEST_FILE);
+    let kept = dir.join("kept.log");
+    let deleted = dir.join("deleted.log");
+    let orphan = dir.join("orphan.log");
+    for file in [&kept, &deleted, &orphan] {
+        std::fs::write(file, "a\n").unwrap();
+    }
+
+    let store = ManifestStore::open(&manifest, &dir).unwrap();
+    // nothing refers to the files yet, so they are all removed
+    assert!(!kept.exists());
+
+    std::fs::write(&kept, "a\n").unwrap();
+    std::fs::write(&deleted, "a\n").unwrap();
+    store.save("kept", user_log(&[&kept]));
+    store.save("deleted", user_log(&[&deleted]));
+    drop(store);
+
+    std::fs::remove_file(&deleted).unwrap();
+    std::fs::write(&orphan, "a\n").unwrap();
+
+    let store = ManifestStore::open(&manifest, &dir).unwrap();
+    // the session of the deleted file is dropped, the orphaned file removed
+    assert_eq!(store.count(), 1);
+    assert!(store.get("kept").is_some());
+    assert!(store.get("deleted").is_none());
+    assert!(kept.exists());
+    assert!(!orphan.exists());
+    // the manifest itself is left alone
+    assert!(manifest.exists());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_concurrent_changes_are_all_persisted() {
+    let dir = temp_dir();
+    let manifest = dir.join(MANIFEST_FILE);
+    let file = dir.join("kept.log");
+
+    let store = ManifestStore::open(&manifest, &dir).unwrap();
+    std::fs::write(&file, "a\nb\nc\n").unwrap();
+    std::thread::scope(|scope| {
+        for thread in 0..8 {
+            let (store, file) = (&store, &file);
+            scope.spawn(move || {
+                for n in 0..10 {
+                    store.save(&format!("{}-{}", thread, n), user_log(&[file]));
+                }
+            });
+        }
+    });
+    drop(store);
+
+    // the last snapshot written contains the changes of every thread
+    let store = ManifestStore::open(&manifest, &dir).unwrap();
+    assert_eq!(store.count(), 80);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_flushed_accesses_survive_reopening() {
+    let dir = temp_dir();
+    let manifest = dir.join(MANIFEST_FILE);
+    let file = dir.join("kept.log");
+
+    let store = ManifestStore::open(&manifest, &dir).unwrap();
+    std::fs::write(&file, "a\nb\nc\n").unwrap();
+    store.save("kept", user_log(&[&file]));
+    let accessed = Utc::now() + chrono::Duration::hours(1);
+    store.touch("kept", accessed);
+    drop(store);
+
+    // an access alone is not written
+    let store = ManifestStore::open(&manifest, &dir).unwrap();
+    assert_ne!(store.get("kept").unwrap().last_access, accessed);
+
+    store.touch("kept", accessed);
+    store.flush();
+    drop(store);
+
+    let store = ManifestStore::open(&manifest, &dir).unwrap();
+    assert_eq!(store.get("kept").unwrap().last_access, accessed);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_sessions_with_several_files() {
+    let dir = temp_dir();
+    let manifest = dir.join(MANIFEST_FILE);
+    let (api, db) = (dir.join("api.log"), dir.join("db.log"));
+
+    let store = ManifestStore::open(&manifest, &dir).unwrap();
+    std::fs::write(&api, "a\n").unwrap();
+    std::fs::write(&db, "b\n").unwrap();
+    store.save("both", user_log(&[&api, &db]));
+    drop(store);
+
+    // all files are kept
+    let store = ManifestStore::open(&manifest, &dir).unwrap();
+    assert_eq!(store.get("both").unwrap().sources.len(), 2);
+    assert!(api.exists() && db.exists());
+    drop(store);
+
+    // a session with a missing file is dropped, its remaining files removed
+    std::fs::remove_file(&db).unwrap();
+    let store = ManifestStore::open(&manifest, &dir).unwrap();
+    assert!(store.get("both").is_none());
+    assert!(!api.exists());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_single_file_sessions_are_read() {
+    let dir = temp_dir();
+    let manifest = dir.join(MANIFEST_FILE);
+    let file = dir.join("old.log");
+    std::fs::write(&file, "a\nb\nc\n").unwrap();
+
+    let old = serde_json::json!({
+        "old": {
+            "path": file.to_string_lossy(),
+            "format": "bracketed",
+            "field_mapping": null,
+            "start_time": null,
+            "stop_time": null,
+            "index": source(&file).index,
+        }
+    });
+    std::fs::write(&manifest, old.to_string()).unwrap();
+
+    let store = ManifestStore::open(&manifest, &dir).unwrap();
+    let log = store.get("old").unwrap();
+    assert_eq!(log.sources.len(), 1);
+    assert_eq!(log.sources[0].path, file.to_string_lossy());
+    assert_eq!(log.sources[0].size, 6);
+    assert_eq!(log.summary.files.len(), 1);
+    assert!(file.exists());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,4 @@
+mod eviction_test;
+mod file_index_test;
+mod log_storage_test;
+mod manifest_test;
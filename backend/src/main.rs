@@ -5,14 +5,20 @@ mod model;
 mod parsing;
 
 use crate::api_doc::ApiDoc;
+use crate::log_storage::eviction::{collect_garbage, SESSION_LIMITS};
+use crate::log_storage::manifest::ManifestStore;
+use crate::log_storage::memory::MemoryStore;
+use crate::log_storage::{flush_user_logs, init_session_store, MANIFEST_FILE, UPLOAD_DIR};
 use axum::{extract::DefaultBodyLimit, Router};
 use tokio::net::TcpListener;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::limit::RequestBodyLimitLayer;
-use tracing_subscriber;
+use tracing::{error, info};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
+use std::path::Path;
+
 #[tokio::main]
 async fn main() {
     tracing_subscriber::fmt()
@@ -20,6 +26,23 @@ async fn main() {
         .with_target(false)
         .init();
 
+    // Restore the sessions of previous runs, without a manifest they are kept in memory only
+    let upload_dir = Path::new(UPLOAD_DIR);
+    match ManifestStore::open(upload_dir.join(MANIFEST_FILE), upload_dir) {
+        Ok(store) => init_session_store(store),
+        Err(e) => {
+            error!(
+                "Failed to open session manifest, sessions will not persist: {:?}",
+                e
+            );
+            init_session_store(MemoryStore::default());
+        }
+    }
+
+    // Evict idle sessions and keep the disk usage and session count within their limits
+    info!("Session limits: {:?}", *SESSION_LIMITS);
+    tokio::spawn(collect_garbage(&SESSION_LIMITS));
+
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
@@ -32,11 +55,48 @@ async fn main() {
         .layer(RequestBodyLimitLayer::new(1024 * 1024 * 1024))
         .merge(api::filter::router())
         .merge(api::stream_filtered_logs::router())
+        .merge(api::seek::router())
+        .merge(api::sessions::router())
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
         .layer(cors);
 
     let listener = TcpListener::bind("0.0.0.0:8080").await.unwrap();
     println!("Listening on http://0.0.0.0:8080");
 
-    axum::serve(listener, app).await.unwrap();
+    // Streams can stay open indefinitely, so stop right away on a shutdown signal instead of
+    // waiting for them. The recorded accesses are persisted first.
+    tokio::select! {
+        result = axum::serve(listener, app) => result.unwrap(),
+        _ = shutdown_signal() => info!("Shutting down"),
+    }
+    flush_user_logs();
+}
+
+/// Completes on Ctrl+C or, on Unix, SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!("Failed to listen for Ctrl+C: {:?}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                error!("Failed to listen for SIGTERM: {:?}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
 }
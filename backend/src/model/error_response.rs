@@ -0,0 +1,11 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Error returned for invalid requests
+#[derive(Serialize, ToSchema)]
+pub struct ErrorResponse {
+    /// Description of the error
+    pub error: String,
+    /// Character position in the offending parameter the error refers to (0-based)
+    pub position: Option<usize>,
+}
@@ -1,5 +1,10 @@
 // local
+use crate::model::log_line::LogLine;
 use crate::model::log_summary::LogSummary;
+use crate::parsing::filter::FilterSpec;
+use crate::parsing::format::ndjson::FieldMapping;
+use crate::parsing::keyword::MatchMode;
+use crate::parsing::level::Severity;
 // serde
 use serde::{Deserialize, Serialize};
 // utoipa
@@ -14,15 +19,98 @@ pub struct FilterRequest {
     /// Optional list of domains to filter by
     pub domains: Option<Vec<String>>,
 
-    /// Optional list of log levels to filter by
+    /// Optional list of log levels to filter by, aliases like "warning" are normalized
     pub levels: Option<Vec<String>>,
+
+    /// Optional minimum severity, e.g. "WARN" keeps WARN, ERROR and FATAL entries
+    pub min_level: Option<Severity>,
+
+    /// Optional name of the log format, defaults to "bracketed"
+    pub format: Option<String>,
+
+    /// Optional mapping of JSON keys for the "ndjson" format
+    pub field_mapping: Option<FieldMapping>,
+
+    /// Optional list of keywords, entries containing any of them are kept
+    pub keywords: Option<Vec<String>>,
+
+    /// Optional conditions on additional fields, e.g. `status>=500` or `component=net`
+    pub fields: Option<Vec<String>>,
+
+    /// Optional start of the time window, an absolute timestamp or relative to the
+    /// start of the log, e.g. "+30s"
+    pub from: Option<String>,
+
+    /// Optional end of the time window, an absolute timestamp or relative to the
+    /// end of the log, e.g. "-15m"
+    pub to: Option<String>,
+
+    /// Optional boolean query, e.g. `level:ERROR AND (domain:network OR domain:core) AND NOT "retrying"`
+    pub query: Option<String>,
+
+    /// Optional list of domains to hide, takes priority over all other filters
+    pub exclude_domains: Option<Vec<String>>,
+
+    /// Optional list of log levels to hide, takes priority over all other filters
+    pub exclude_levels: Option<Vec<String>>,
+
+    /// Optional list of keywords to hide, matched according to `match_mode`
+    pub exclude_keywords: Option<Vec<String>>,
+
+    /// How keywords in the query are matched, defaults to literal
+    pub match_mode: Option<MatchMode>,
+
+    /// Optional number of surrounding entries to include around each match
+    pub context: Option<usize>,
+
+    /// Optional number of entries to include before each match, overrides `context`
+    pub before: Option<usize>,
+
+    /// Optional number of entries to include after each match, overrides `context`
+    pub after: Option<usize>,
+}
+
+impl FilterRequest {
+    /// Number of entries to include before and after each match
+    pub fn context_window(&self) -> (usize, usize) {
+        let context = self.context.unwrap_or(0);
+        (
+            self.before.unwrap_or(context),
+            self.after.unwrap_or(context),
+        )
+    }
+
+    /// Filter options of the request
+    pub fn filter_spec(&self) -> FilterSpec {
+        FilterSpec {
+            domains: self.domains.clone(),
+            levels: self.levels.clone(),
+            min_level: self.min_level,
+            keywords: self.keywords.clone(),
+            match_mode: self.match_mode.unwrap_or_default(),
+            fields: self.fields.clone().unwrap_or_default(),
+            from: self.from.clone(),
+            to: self.to.clone(),
+            query: self.query.clone(),
+            exclude_domains: self.exclude_domains.clone().unwrap_or_default(),
+            exclude_levels: self.exclude_levels.clone().unwrap_or_default(),
+            exclude_keywords: self.exclude_keywords.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Adjacent entries, i.e. matches together with their context
+#[derive(Serialize, ToSchema)]
+pub struct Hunk {
+    pub lines: Vec<LogLine>,
 }
 
 /// Response after filtering logs
 #[derive(Serialize, ToSchema)]
 pub struct FilteredLogResponse {
-    /// Lines of the log that match the filter
-    pub filtered_lines: Vec<String>,
+    /// Matching entries with their context, grouped into hunks of adjacent entries. Without
+    /// context all matches are in one hunk.
+    pub hunks: Vec<Hunk>,
 
     /// Summary of the filtered log
     pub summary: LogSummary,
@@ -0,0 +1,65 @@
+// local
+use crate::parsing::entry::LogEntry;
+use crate::parsing::filter::Filter;
+// serde
+use serde::Serialize;
+// utoipa
+use utoipa::ToSchema;
+
+/// Range of a keyword hit in a message, in UTF-16 code units like JavaScript string indices, so
+/// `message.slice(start, end)` is the hit
+#[derive(Serialize, ToSchema)]
+pub struct MatchSpan {
+    /// Position of the first code unit of the hit (0-based)
+    pub start: usize,
+    /// Position after the last code unit of the hit
+    pub end: usize,
+}
+
+/// A log entry as sent to clients
+#[derive(Serialize, ToSchema)]
+pub struct LogLine {
+    /// Raw text of the entry, multi-line entries are joined by newlines
+    pub line: String,
+    /// Whether the entry is only included as context of a match
+    pub context: bool,
+    /// Line number of the entry in the file (1-based)
+    pub line_number: usize,
+    /// Byte offset of the entry in the file
+    pub byte_offset: u64,
+    /// Timestamp as it appears in the entry
+    pub ts: Option<String>,
+    /// Normalized log level
+    pub level: Option<String>,
+    /// Domain the entry was logged from
+    pub domain: Option<String>,
+    /// Message including continuation lines
+    pub message: String,
+    /// Keyword and regex hits in the message, sorted and not overlapping
+    pub highlights: Vec<MatchSpan>,
+    /// Name of the file the entry belongs to, for entries of an uploaded session
+    pub source: Option<String>,
+}
+
+impl LogLine {
+    /// Convert an entry, highlights are the hits of the filter's keywords
+    pub fn new(entry: LogEntry, context: bool, filter: &Filter) -> Self {
+        let highlights = filter
+            .highlights(&entry.record.message)
+            .into_iter()
+            .map(|(start, end)| MatchSpan { start, end })
+            .collect();
+        Self {
+            line: entry.text(),
+            context,
+            line_number: entry.line_number,
+            byte_offset: entry.byte_offset,
+            ts: entry.record.timestamp,
+            level: entry.record.level,
+            domain: entry.record.domain,
+            message: entry.record.message,
+            highlights,
+            source: None,
+        }
+    }
+}
@@ -1,20 +1,54 @@
+// chrono
+use chrono::{DateTime, Utc};
+// indexmap
+use indexmap::IndexMap;
 // serde
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 // utoipa
 use utoipa::ToSchema;
 // std
 use std::collections::HashMap;
 
-#[derive(Serialize, ToSchema, Debug)]
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Default)]
 pub struct LogSummary {
-    /// Total number of lines in the log
+    /// Total number of log entries, an entry spanning multiple lines is counted once
     pub total_lines: usize,
-    /// Count of log entries per level, e.g., {"INFO": 123, "WARN": 5}
-    pub levels: HashMap<String, usize>,
+    /// Count of log entries per normalized level in severity order, e.g., {"INFO": 123, "WARN": 5}
+    pub levels: IndexMap<String, usize>,
     /// List of unique domains found in the log
     pub unique_domains: Vec<String>,
-    /// Timestamp of the first log entry
+    /// Count of log entries per syslog facility, e.g., {"auth": 12, "cron": 3}
+    pub facilities: HashMap<String, usize>,
+    /// List of unique hosts found in the log
+    pub unique_hosts: Vec<String>,
+    /// Timestamp of the earliest log entry as it appears in the log
     pub start_timestamp: Option<String>,
-    /// Timestamp of the last log entry
+    /// Timestamp of the latest log entry as it appears in the log
     pub stop_timestamp: Option<String>,
+    /// Parsed timestamp of the earliest log entry
+    pub start_time: Option<DateTime<Utc>>,
+    /// Parsed timestamp of the latest log entry
+    pub stop_time: Option<DateTime<Utc>>,
+    /// Time between the earliest and the latest log entry in seconds
+    pub duration_seconds: Option<f64>,
+    /// Statistics per file of a session with several files
+    #[serde(default)]
+    pub files: Vec<FileSummary>,
+}
+
+/// Statistics of one file of a session
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Default)]
+pub struct FileSummary {
+    /// Name entries of the file are tagged with
+    pub source: String,
+    /// Name of the log format used to parse the file
+    pub format: String,
+    /// Number of log entries in the file
+    pub total_lines: usize,
+    /// Count of log entries per normalized level in severity order
+    pub levels: IndexMap<String, usize>,
+    /// Parsed timestamp of the earliest log entry in the file
+    pub start_time: Option<DateTime<Utc>>,
+    /// Parsed timestamp of the latest log entry in the file
+    pub stop_time: Option<DateTime<Utc>>,
 }
@@ -1,4 +1,8 @@
 pub mod close_session_query;
+pub mod error_response;
 pub mod filter;
+pub mod log_line;
 pub mod log_summary;
+pub mod seek_response;
+pub mod session_info;
 pub mod upload_response;
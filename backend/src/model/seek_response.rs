@@ -0,0 +1,16 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Position of the first entry at or after a timestamp
+#[derive(Serialize, ToSchema)]
+pub struct SeekResponse {
+    /// Name of the file the entry belongs to
+    pub source: String,
+    /// Line number of the entry in the file (1-based)
+    pub line_number: usize,
+    /// Byte offset of the entry in its file. A `cursor` resumes strictly after the entry at its
+    /// offset, to stream from this entry on pass the timestamp as `start_at` instead.
+    pub byte_offset: u64,
+    /// Timestamp as it appears in the entry
+    pub ts: Option<String>,
+}
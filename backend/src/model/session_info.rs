@@ -0,0 +1,72 @@
+use crate::log_storage::{LogSource, UserLog};
+use crate::model::log_summary::LogSummary;
+// chrono
+use chrono::{DateTime, Utc};
+// serde
+use serde::Serialize;
+// utoipa
+use utoipa::ToSchema;
+
+/// An uploaded log a user can return to
+#[derive(Serialize, ToSchema)]
+pub struct SessionInfo {
+    /// Unique session identifier
+    pub session_id: String,
+    /// Uploaded files, their entries are merged by timestamp
+    pub files: Vec<SessionFile>,
+    /// Total size of the files in bytes
+    pub size: u64,
+    /// Total number of lines in the files
+    pub line_count: usize,
+    /// When the files were uploaded
+    pub uploaded_at: DateTime<Utc>,
+    /// When the session was last used
+    pub last_access: DateTime<Utc>,
+    /// Summary of all files with statistics per file
+    pub summary: LogSummary,
+}
+
+/// One uploaded file of a session
+#[derive(Serialize, ToSchema)]
+pub struct SessionFile {
+    /// Name entries of the file are tagged with, can be used to filter by file
+    pub source: String,
+    /// Name of the file as uploaded
+    pub file_name: Option<String>,
+    /// Size of the file in bytes
+    pub size: u64,
+    /// Number of lines in the file
+    pub line_count: usize,
+    /// SHA-256 of the file content, hex encoded
+    pub sha256: String,
+    /// Name of the log format used to parse the file
+    pub format: String,
+}
+
+impl From<LogSource> for SessionFile {
+    fn from(source: LogSource) -> Self {
+        Self {
+            source: source.name,
+            file_name: source.file_name,
+            size: source.size,
+            line_count: source.index.total_lines,
+            sha256: source.sha256,
+            format: source.format,
+        }
+    }
+}
+
+impl SessionInfo {
+    pub fn new(session_id: String, log: UserLog) -> Self {
+        let files: Vec<SessionFile> = log.sources.into_iter().map(SessionFile::from).collect();
+        Self {
+            session_id,
+            size: files.iter().map(|file| file.size).sum(),
+            line_count: files.iter().map(|file| file.line_count).sum(),
+            files,
+            uploaded_at: log.uploaded_at,
+            last_access: log.last_access,
+            summary: log.summary,
+        }
+    }
+}
@@ -6,8 +6,25 @@ use utoipa::ToSchema;
 
 #[derive(Serialize, ToSchema)]
 pub struct UploadResponse {
-    // Unique session identifier for the uploaded log
+    /// Unique session identifier for the uploaded log
     pub session_id: String,
-    // Summary of the uploaded log
+    /// Name of the log format used to parse the first file, see `files` for the others
+    pub format: String,
+    /// Share of sampled lines of the first file matching its format, between 0 and 1
+    pub confidence: f32,
+    /// Log format of each uploaded file, in upload order
+    pub files: Vec<FileFormat>,
+    /// Summary of the uploaded log
     pub summary: LogSummary,
 }
+
+/// Log format used to parse one uploaded file
+#[derive(Serialize, ToSchema)]
+pub struct FileFormat {
+    /// Name entries of the file are tagged with
+    pub source: String,
+    /// Name of the log format used to parse the file
+    pub format: String,
+    /// Share of sampled lines matching the format, between 0 and 1
+    pub confidence: f32,
+}
@@ -0,0 +1,85 @@
+// std
+use std::collections::VecDeque;
+
+/// Output of a [`ContextWindow`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContextEvent<T> {
+    /// An entry to show, `context` is true for entries shown only because they surround a match
+    Line { entry: T, context: bool },
+    /// Gap between two hunks that are not adjacent, like `--` in grep. Only emitted if context
+    /// is requested, plain matches are never separated.
+    Separator,
+}
+
+/// Expands matching entries by up to `before` preceding and `after` following entries, like
+/// `grep -B/-A`. Overlapping or adjacent windows are merged into one hunk.
+#[derive(Debug)]
+pub struct ContextWindow<T> {
+    before: usize,
+    after: usize,
+    /// Not yet shown entries preceding the next match with their index, at most `before`
+    pending: VecDeque<(usize, T)>,
+    /// Number of upcoming entries still to show as context of the last match
+    after_remaining: usize,
+    /// Index of the last entry shown
+    last_shown: Option<usize>,
+    /// Index of the next entry
+    next: usize,
+}
+
+impl<T> ContextWindow<T> {
+    pub fn new(before: usize, after: usize) -> Self {
+        Self {
+            before,
+            after,
+            pending: VecDeque::with_capacity(before),
+            after_remaining: 0,
+            last_shown: None,
+            next: 0,
+        }
+    }
+
+    /// Feed the next entry, returns the events to emit in order
+    pub fn push(&mut self, entry: T, matched: bool) -> Vec<ContextEvent<T>> {
+        let index = self.next;
+        self.next += 1;
+
+        if matched {
+            let mut events = Vec::new();
+            let first = self.pending.front().map_or(index, |(i, _)| *i);
+            let has_context = self.before + self.after > 0;
+            if has_context && self.last_shown.is_some_and(|last| first > last + 1) {
+                events.push(ContextEvent::Separator);
+            }
+            events.extend(self.pending.drain(..).map(|(_, entry)| ContextEvent::Line {
+                entry,
+                context: true,
+            }));
+            events.push(ContextEvent::Line {
+                entry,
+                context: false,
+            });
+            self.after_remaining = self.after;
+            self.last_shown = Some(index);
+            events
+        } else if self.after_remaining > 0 {
+            self.after_remaining -= 1;
+            self.last_shown = Some(index);
+            vec![ContextEvent::Line {
+                entry,
+                context: true,
+            }]
+        } else {
+            if self.before > 0 {
+                self.pending.push_back((index, entry));
+                while self.pending.len() > self.before {
+                    self.pending.pop_front();
+                }
+            }
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,62 @@
+use super::super::*;
+
+/// Run the window over lines, a line starting with "!" matches. Lines are rendered like grep,
+/// `>` marks matches, `-` context and `--` separators.
+fn run(before: usize, after: usize, lines: &[&str]) -> Vec<String> {
+    let mut window = ContextWindow::new(before, after);
+    lines
+        .iter()
+        .flat_map(|line| window.push(line.to_string(), line.starts_with('!')))
+        .map(|event| match event {
+            ContextEvent::Line {
+                entry,
+                context: false,
+            } => format!("> {}", entry),
+            ContextEvent::Line {
+                entry,
+                context: true,
+            } => format!("- {}", entry),
+            ContextEvent::Separator => "--".to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_no_context_has_no_separators() {
+    assert_eq!(
+        run(0, 0, &["a", "!b", "!c", "d", "!e"]),
+        vec!["> !b", "> !c", "> !e"]
+    );
+}
+
+#[test]
+fn test_separator_between_non_adjacent_hunks() {
+    assert_eq!(
+        run(0, 1, &["!a", "b", "c", "!d"]),
+        vec!["> !a", "- b", "--", "> !d"]
+    );
+}
+
+#[test]
+fn test_asymmetric_context() {
+    assert_eq!(
+        run(2, 1, &["a", "b", "c", "!d", "e", "f", "g"]),
+        vec!["- b", "- c", "> !d", "- e"]
+    );
+}
+
+#[test]
+fn test_overlapping_windows_are_merged() {
+    assert_eq!(
+        run(1, 1, &["a", "!b", "c", "d", "!e", "f", "g", "h", "!i"]),
+        vec!["- a", "> !b", "- c", "- d", "> !e", "- f", "--", "- h", "> !i"]
+    );
+}
+
+#[test]
+fn test_match_within_after_context_extends_hunk() {
+    assert_eq!(
+        run(0, 2, &["!a", "b", "!c", "d", "e", "f"]),
+        vec!["> !a", "- b", "> !c", "- d", "- e"]
+    );
+}
@@ -0,0 +1 @@
+mod context_test;
@@ -0,0 +1,65 @@
+// local
+use crate::parsing::format::{default_format, LogFormat, FORMATS};
+// std
+use std::sync::Arc;
+
+/// Number of lines at the start of a log used to detect its format
+pub const SAMPLE_LINES: usize = 500;
+
+/// Result of a format detection
+pub struct Detection {
+    /// The best matching format
+    pub format: Arc<dyn LogFormat>,
+    /// Share of sampled non-empty lines the format could parse, between 0 and 1
+    pub confidence: f32,
+}
+
+/// Score every known format against the sample and return the best one.
+/// Falls back to the default format with a confidence of 0 if nothing matches.
+pub fn detect_format<'a>(sample: impl IntoIterator<Item = &'a str>) -> Detection {
+    let lines: Vec<&str> = sample
+        .into_iter()
+        .filter(|line| !line.trim().is_empty())
+        .take(SAMPLE_LINES)
+        .collect();
+
+    let mut best = Detection {
+        format: default_format(),
+        confidence: 0.0,
+    };
+    if lines.is_empty() {
+        return best;
+    }
+
+    for format in FORMATS.iter() {
+        let confidence = score_format(format.as_ref(), &lines);
+        // strictly greater, so earlier formats in the registry win ties
+        if confidence > best.confidence {
+            best = Detection {
+                format: format.clone(),
+                confidence,
+            };
+        }
+    }
+
+    best
+}
+
+/// Share of non-empty lines the format is able to parse
+pub fn score_format(format: &dyn LogFormat, lines: &[&str]) -> f32 {
+    let mut total = 0;
+    let mut parsed = 0;
+    for line in lines.iter().filter(|line| !line.trim().is_empty()) {
+        total += 1;
+        if format.parse_line(line).is_some() {
+            parsed += 1;
+        }
+    }
+    if total == 0 {
+        return 0.0;
+    }
+    parsed as f32 / total as f32
+}
+
+#[cfg(test)]
+mod tests;
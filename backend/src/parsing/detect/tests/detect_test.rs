@@ -0,0 +1,53 @@
+use super::super::*;
+use crate::parsing::format::bracketed::BracketedFormat;
+
+#[test]
+fn test_detect_bracketed_log() {
+    let log = [
+        "[2025-11-23 10:00:00] [INFO] [core] Starting process",
+        "",
+        "[2025-11-23 10:00:01] [DEBUG] [network] Connection established",
+        "not a log line",
+        "[2025-11-23 10:00:02] [WARN] [core] Unexpected value encountered",
+        "[2025-11-23 10:00:03] [ERROR] [data_acq] Data processing failed",
+    ];
+
+    let detection = detect_format(log);
+
+    assert_eq!(detection.format.name(), "bracketed");
+    assert_eq!(detection.confidence, 0.8);
+}
+
+#[test]
+fn test_detect_unknown_log_falls_back_to_default() {
+    let detection = detect_format(["hello", "world"]);
+
+    assert_eq!(detection.format.name(), default_format().name());
+    assert_eq!(detection.confidence, 0.0);
+}
+
+#[test]
+fn test_detect_ndjson_log() {
+    let log = [
+        r#"{"ts":"2025-11-23T10:00:00Z","level":"info","msg":"Starting process"}"#,
+        r#"{"ts":"2025-11-23T10:00:01Z","level":"error","msg":"Failed"}"#,
+    ];
+
+    let detection = detect_format(log);
+
+    assert_eq!(detection.format.name(), "ndjson");
+    assert_eq!(detection.confidence, 1.0);
+}
+
+#[test]
+fn test_score_format_ignores_blank_lines() {
+    let log = [
+        "[2025-11-23 10:00:00] [INFO] [core] Starting process",
+        "",
+        "   ",
+        "[2025-11-23 10:00:01] [DEBUG] [network] Connection established",
+    ];
+
+    assert_eq!(score_format(&BracketedFormat, &log), 1.0);
+    assert_eq!(score_format(&BracketedFormat, &["", " "]), 0.0);
+}
@@ -0,0 +1,105 @@
+// local
+use crate::parsing::format::LogFormat;
+use crate::parsing::record::LogRecord;
+
+/// A complete log entry, i.e. a header line followed by its continuation lines
+/// (stack traces, wrapped messages, ...)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    /// Structured parts of the header line, the message includes the continuation lines
+    pub record: LogRecord,
+    /// All raw lines of the entry, starting with the header line
+    pub lines: Vec<String>,
+    /// Line number of the header line in the file (1-based)
+    pub line_number: usize,
+    /// Byte offset of the header line in the file
+    pub byte_offset: u64,
+}
+
+impl LogEntry {
+    /// Raw text of the entry with its lines joined by newlines
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+}
+
+/// Groups lines into entries. Lines the format can not parse are attached to the previous entry,
+/// lines before the first parseable line are dropped.
+#[derive(Default)]
+pub struct EntryAssembler {
+    pending: Option<LogEntry>,
+    /// Number of lines fed so far
+    line_count: usize,
+    /// Byte offset of the next line
+    next_offset: u64,
+}
+
+impl EntryAssembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Assembler for a file read from the start of the given line onwards
+    pub fn starting_at(line_number: usize, byte_offset: u64) -> Self {
+        Self {
+            pending: None,
+            line_count: line_number.saturating_sub(1),
+            next_offset: byte_offset,
+        }
+    }
+
+    /// Feed the next line without its `\n`, returns the previous entry once the line shows it
+    /// is complete. `raw_len` is the length of the line in the file, which differs from the
+    /// length of `line` if invalid UTF-8 was replaced. A trailing `\r` is stripped but still
+    /// counted for the byte offsets.
+    pub fn push(
+        &mut self,
+        format: &dyn LogFormat,
+        mut line: String,
+        raw_len: usize,
+    ) -> Option<LogEntry> {
+        self.line_count += 1;
+        let byte_offset = self.next_offset;
+        self.next_offset += raw_len as u64 + 1;
+        if line.ends_with('\r') {
+            line.pop();
+        }
+
+        match format.parse_record(&line) {
+            Some(record) => self.pending.replace(LogEntry {
+                record,
+                lines: vec![line],
+                line_number: self.line_count,
+                byte_offset,
+            }),
+            None => {
+                if let Some(entry) = self.pending.as_mut() {
+                    entry.record.message.push('\n');
+                    entry.record.message.push_str(&line);
+                    entry.lines.push(line);
+                }
+                None
+            }
+        }
+    }
+
+    /// Return the last entry at the end of the input
+    pub fn finish(&mut self) -> Option<LogEntry> {
+        self.pending.take()
+    }
+}
+
+/// Split a whole log text into entries
+pub fn parse_entries(format: &dyn LogFormat, log_text: &str) -> Vec<LogEntry> {
+    let mut assembler = EntryAssembler::new();
+    let mut entries: Vec<LogEntry> = log_text
+        .split_inclusive('\n')
+        .map(|line| line.strip_suffix('\n').unwrap_or(line))
+        .filter_map(|line| assembler.push(format, line.to_string(), line.len()))
+        .collect();
+    entries.extend(assembler.finish());
+    entries
+}
+
+#[cfg(test)]
+mod tests;
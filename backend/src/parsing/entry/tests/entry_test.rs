@@ -0,0 +1,77 @@
+use super::super::*;
+use crate::parsing::format::bracketed::BracketedFormat;
+
+#[test]
+fn test_continuation_lines_are_attached_to_previous_entry() {
+    let log = [
+        "[2025-11-23 10:00:00] [INFO] [core] Starting process",
+        "[2025-11-23 10:00:01] [ERROR] [core] thread 'main' panicked at src/main.rs:4:5:",
+        "stack backtrace:",
+        "   0: rust_begin_unwind",
+        "[2025-11-23 10:00:02] [INFO] [core] Restarted",
+    ]
+    .join("\n");
+
+    let entries = parse_entries(&BracketedFormat, &log);
+
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[1].lines.len(), 3);
+    assert_eq!(entries[1].record.level.as_deref(), Some("ERROR"));
+    assert!(entries[1].record.message.ends_with("rust_begin_unwind"));
+    assert_eq!(
+        entries[2].text(),
+        "[2025-11-23 10:00:02] [INFO] [core] Restarted"
+    );
+}
+
+#[test]
+fn test_lines_before_first_entry_are_dropped() {
+    let log = "garbage\n[2025-11-23 10:00:00] [INFO] [core] Starting process\n  trailing";
+
+    let entries = parse_entries(&BracketedFormat, log);
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].lines.len(), 2);
+}
+
+#[test]
+fn test_entries_know_their_position() {
+    let log = "garbage\r\n[2025-11-23 10:00:00] [INFO] [core] Start\r\n  more\n[2025-11-23 10:00:01] [INFO] [core] Next\n";
+
+    let entries = parse_entries(&BracketedFormat, log);
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].line_number, 2);
+    assert_eq!(entries[0].byte_offset, 9);
+    assert_eq!(
+        entries[0].lines[0],
+        "[2025-11-23 10:00:00] [INFO] [core] Start"
+    );
+    assert_eq!(entries[1].line_number, 4);
+    assert_eq!(
+        &log[entries[1].byte_offset as usize..],
+        "[2025-11-23 10:00:01] [INFO] [core] Next\n"
+    );
+}
+
+#[test]
+fn test_invalid_utf8_keeps_byte_offsets() {
+    let log: &[u8] = b"[2025-11-23 10:00:00] [INFO] [core] bad \xff\xfe byte\n[2025-11-23 10:00:01] [INFO] [core] Next\n";
+
+    let mut assembler = EntryAssembler::new();
+    let mut entries = Vec::new();
+    for raw in log.split_inclusive(|&b| b == b'\n') {
+        let raw = raw.strip_suffix(b"\n").unwrap_or(raw);
+        let line = String::from_utf8_lossy(raw).into_owned();
+        entries.extend(assembler.push(&BracketedFormat, line, raw.len()));
+    }
+    entries.extend(assembler.finish());
+
+    assert_eq!(entries.len(), 2);
+    // each invalid byte became the 3-byte replacement character
+    assert!(entries[0].record.message.contains('\u{FFFD}'));
+    assert_eq!(
+        &log[entries[1].byte_offset as usize..],
+        b"[2025-11-23 10:00:01] [INFO] [core] Next\n"
+    );
+}
@@ -0,0 +1 @@
+mod entry_test;
@@ -0,0 +1,49 @@
+// local
+use crate::parsing::keyword::{KeywordMatcher, MatchMode};
+use crate::parsing::level::normalize_level;
+use crate::parsing::record::LogRecord;
+
+/// Domains, levels and keywords to hide, exclusions take priority over all other filters
+#[derive(Debug, Clone, Default)]
+pub struct Exclusions {
+    domains: Vec<String>,
+    levels: Vec<String>,
+    keywords: Vec<KeywordMatcher>,
+}
+
+impl Exclusions {
+    /// Build exclusions, levels are normalized and keywords compiled for the match mode. Empty
+    /// items are ignored, an empty keyword would hide every entry.
+    pub fn new(
+        domains: &[String],
+        levels: &[String],
+        keywords: &[String],
+        mode: MatchMode,
+    ) -> Result<Self, String> {
+        let given = |item: &&String| !item.trim().is_empty();
+        Ok(Self {
+            domains: domains.iter().filter(given).cloned().collect(),
+            levels: levels
+                .iter()
+                .filter(given)
+                .map(|level| normalize_level(level))
+                .collect(),
+            keywords: keywords
+                .iter()
+                .filter(given)
+                .map(|keyword| KeywordMatcher::new(keyword, mode))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Whether the record has an excluded domain or level, or its message (including
+    /// continuation lines) contains an excluded keyword
+    pub fn excludes(&self, record: &LogRecord) -> bool {
+        self.domains.iter().any(|d| d == record.domain_str())
+            || self.levels.iter().any(|l| l == record.level_str())
+            || self.keywords.iter().any(|kw| kw.is_match(&record.message))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,54 @@
+use super::super::*;
+
+fn record(level: &str, domain: &str, message: &str) -> LogRecord {
+    LogRecord {
+        level: Some(level.to_string()),
+        domain: Some(domain.to_string()),
+        message: message.to_string(),
+        ..Default::default()
+    }
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_excludes_domains_levels_and_keywords() {
+    let exclusions = Exclusions::new(
+        &strings(&["network"]),
+        &strings(&["debug"]),
+        &strings(&["heartbeat"]),
+        MatchMode::Literal,
+    )
+    .unwrap();
+
+    assert!(exclusions.excludes(&record("INFO", "network", "Connected")));
+    assert!(exclusions.excludes(&record("DEBUG", "core", "Tick")));
+    assert!(exclusions.excludes(&record("INFO", "core", "sent heartbeat")));
+    assert!(!exclusions.excludes(&record("INFO", "core", "Started")));
+    assert!(!Exclusions::default().excludes(&record("INFO", "network", "heartbeat")));
+}
+
+#[test]
+fn test_invalid_keyword_regex_is_rejected() {
+    assert!(Exclusions::new(&[], &[], &strings(&["beat("]), MatchMode::Regex).is_err());
+}
+
+#[test]
+fn test_empty_exclusions_are_ignored() {
+    let exclusions = Exclusions::new(
+        &strings(&["network", ""]),
+        &strings(&[" "]),
+        &strings(&[""]),
+        MatchMode::Literal,
+    )
+    .unwrap();
+
+    assert!(exclusions.excludes(&record("INFO", "network", "Connected")));
+    assert!(!exclusions.excludes(&record("INFO", "core", "Started")));
+    assert!(!exclusions.excludes(&LogRecord {
+        message: "no domain".to_string(),
+        ..Default::default()
+    }));
+}
@@ -0,0 +1 @@
+mod exclusion_test;
@@ -0,0 +1,89 @@
+// local
+use crate::parsing::record::{FieldValue, LogRecord};
+// std
+use std::cmp::Ordering;
+
+/// Comparison operator of a [`FieldFilter`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Condition on an additional field of a record, e.g. `status>=500` or `component=net`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldFilter {
+    pub key: String,
+    pub op: FieldOp,
+    pub value: FieldValue,
+}
+
+impl FieldFilter {
+    /// Parse a condition in the form `key<op>value` with op one of `=`, `!=`, `<`, `<=`, `>`, `>=`
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let start = text
+            .find(['=', '!', '<', '>'])
+            .ok_or_else(|| format!("Invalid field filter '{}': missing operator", text))?;
+        let rest = &text[start..];
+        let (op, len) = if rest.starts_with("!=") {
+            (FieldOp::Ne, 2)
+        } else if rest.starts_with("<=") {
+            (FieldOp::Le, 2)
+        } else if rest.starts_with(">=") {
+            (FieldOp::Ge, 2)
+        } else if rest.starts_with('=') {
+            (FieldOp::Eq, 1)
+        } else if rest.starts_with('<') {
+            (FieldOp::Lt, 1)
+        } else if rest.starts_with('>') {
+            (FieldOp::Gt, 1)
+        } else {
+            return Err(format!("Invalid field filter '{}': unknown operator", text));
+        };
+
+        let key = text[..start].trim();
+        if key.is_empty() {
+            return Err(format!(
+                "Invalid field filter '{}': missing field name",
+                text
+            ));
+        }
+
+        Ok(Self {
+            key: key.to_string(),
+            op,
+            value: FieldValue::infer(text[start + len..].trim()),
+        })
+    }
+
+    /// Whether the record has the field and it satisfies the condition. Numbers are compared
+    /// numerically, everything else by its text.
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        let Some(actual) = record.fields.get(&self.key) else {
+            return false;
+        };
+        let ordering = match (actual, &self.value) {
+            (FieldValue::Number(a), FieldValue::Number(b)) => a.partial_cmp(b),
+            (a, b) => Some(a.to_string().cmp(&b.to_string())),
+        };
+        let Some(ordering) = ordering else {
+            return false;
+        };
+
+        match self.op {
+            FieldOp::Eq => ordering == Ordering::Equal,
+            FieldOp::Ne => ordering != Ordering::Equal,
+            FieldOp::Lt => ordering == Ordering::Less,
+            FieldOp::Le => ordering != Ordering::Greater,
+            FieldOp::Gt => ordering == Ordering::Greater,
+            FieldOp::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
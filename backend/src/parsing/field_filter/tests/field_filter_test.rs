@@ -0,0 +1,42 @@
+use super::super::*;
+
+fn record() -> LogRecord {
+    let mut record = LogRecord::default();
+    record
+        .fields
+        .insert("status".to_string(), FieldValue::Number(503.0));
+    record.fields.insert(
+        "peer".to_string(),
+        FieldValue::String("10.0.0.5".to_string()),
+    );
+    record
+}
+
+#[test]
+fn test_numeric_comparison() {
+    let record = record();
+
+    assert!(FieldFilter::parse("status>=500").unwrap().matches(&record));
+    assert!(FieldFilter::parse("status > 99").unwrap().matches(&record));
+    assert!(!FieldFilter::parse("status<500").unwrap().matches(&record));
+    assert!(FieldFilter::parse("status=503").unwrap().matches(&record));
+}
+
+#[test]
+fn test_text_comparison_and_missing_fields() {
+    let record = record();
+
+    assert!(FieldFilter::parse("peer=10.0.0.5")
+        .unwrap()
+        .matches(&record));
+    assert!(FieldFilter::parse("peer!=10.0.0.6")
+        .unwrap()
+        .matches(&record));
+    assert!(!FieldFilter::parse("missing!=1").unwrap().matches(&record));
+}
+
+#[test]
+fn test_parse_rejects_invalid_filters() {
+    assert!(FieldFilter::parse("status").is_err());
+    assert!(FieldFilter::parse("=5").is_err());
+}
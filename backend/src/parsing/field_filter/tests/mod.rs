@@ -0,0 +1 @@
+mod field_filter_test;
@@ -0,0 +1,210 @@
+// local
+use crate::parsing::exclusion::Exclusions;
+use crate::parsing::field_filter::FieldFilter;
+use crate::parsing::keyword::{KeywordMatcher, MatchMode};
+use crate::parsing::level::{is_at_least, normalize_level, Severity};
+use crate::parsing::query::{Query, QueryError};
+use crate::parsing::record::LogRecord;
+use crate::parsing::time_range::TimeRange;
+// chrono
+use chrono::{DateTime, Utc};
+
+/// Filter options as given by a client, see [`Filter::compile`]
+#[derive(Debug, Clone, Default)]
+pub struct FilterSpec {
+    pub domains: Option<Vec<String>>,
+    pub levels: Option<Vec<String>>,
+    pub min_level: Option<Severity>,
+    pub keywords: Option<Vec<String>>,
+    pub match_mode: MatchMode,
+    /// Conditions on additional fields, e.g. `status>=500`
+    pub fields: Vec<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub query: Option<String>,
+    pub exclude_domains: Vec<String>,
+    pub exclude_levels: Vec<String>,
+    pub exclude_keywords: Vec<String>,
+}
+
+/// Error while compiling a [`FilterSpec`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterError {
+    /// The boolean query is invalid
+    Query(QueryError),
+    /// Any other option is invalid, e.g. a keyword regex or a time
+    Invalid(String),
+}
+
+/// A compiled filter deciding which log records are kept. The same filter gives the same
+/// result for `/filter`, `/stream_logs` and the log summary.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    domains: Option<Vec<String>>,
+    levels: Option<Vec<String>>,
+    min_level: Option<Severity>,
+    keywords: Option<Vec<KeywordMatcher>>,
+    fields: Vec<FieldFilter>,
+    time_range: TimeRange,
+    query: Option<Query>,
+    exclusions: Exclusions,
+}
+
+impl Filter {
+    /// Compile the options once, relative times are resolved against the time span of the log
+    pub fn compile(
+        spec: &FilterSpec,
+        log_start: Option<DateTime<Utc>>,
+        log_end: Option<DateTime<Utc>>,
+    ) -> Result<Self, FilterError> {
+        let keywords = spec
+            .keywords
+            .as_ref()
+            .map(|keywords| {
+                keywords
+                    .iter()
+                    .map(|keyword| KeywordMatcher::new(keyword, spec.match_mode))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()
+            .map_err(FilterError::Invalid)?;
+
+        let fields = spec
+            .fields
+            .iter()
+            .filter(|field| !field.trim().is_empty())
+            .map(|field| FieldFilter::parse(field))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(FilterError::Invalid)?;
+
+        let query = spec
+            .query
+            .as_deref()
+            .filter(|q| !q.trim().is_empty())
+            .map(|text| Query::parse(text, spec.match_mode))
+            .transpose()
+            .map_err(FilterError::Query)?;
+
+        let time_range =
+            TimeRange::parse(spec.from.as_deref(), spec.to.as_deref(), log_start, log_end)
+                .map_err(FilterError::Invalid)?;
+
+        let exclusions = Exclusions::new(
+            &spec.exclude_domains,
+            &spec.exclude_levels,
+            &spec.exclude_keywords,
+            spec.match_mode,
+        )
+        .map_err(FilterError::Invalid)?;
+
+        Ok(Self {
+            domains: spec.domains.clone(),
+            levels: spec
+                .levels
+                .as_ref()
+                .map(|levels| levels.iter().map(|level| normalize_level(level)).collect()),
+            min_level: spec.min_level,
+            keywords,
+            fields,
+            time_range,
+            query,
+            exclusions,
+        })
+    }
+
+    /// Whether the record is kept, exclusions take priority over everything else. Level and
+    /// domain come from the header line, keywords may also match continuation lines.
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        let level = record.level_str();
+        let domain = record.domain_str();
+
+        !self.exclusions.excludes(record)
+            && self
+                .levels
+                .as_ref()
+                .is_none_or(|l| l.iter().any(|x| x == level))
+            && self.min_level.is_none_or(|min| is_at_least(level, min))
+            && self
+                .domains
+                .as_ref()
+                .is_none_or(|d| d.iter().any(|x| x == domain))
+            && self
+                .keywords
+                .as_ref()
+                .is_none_or(|k| k.iter().any(|kw| kw.is_match(&record.message)))
+            && self.fields.iter().all(|f| f.matches(record))
+            && self.time_range.contains(record)
+            && self.query.as_ref().is_none_or(|q| q.matches(record))
+    }
+
+    /// Ranges `[start, end)` of all keyword hits in the message, from the keyword list and the
+    /// query. Overlapping hits are merged. Positions count UTF-16 code units like JavaScript
+    /// string indices, so characters outside the BMP (e.g. emoji) count twice.
+    pub fn highlights(&self, message: &str) -> Vec<(usize, usize)> {
+        let keywords = self.keywords.iter().flatten();
+        let query_keywords = self.query.iter().flat_map(|q| q.positive_keywords());
+        let mut hits: Vec<(usize, usize)> = keywords
+            .chain(query_keywords)
+            .flat_map(|keyword| keyword.find_all(message))
+            .collect();
+        hits.sort_unstable();
+
+        let mut merged: Vec<(usize, usize)> = Vec::new();
+        for (start, end) in hits {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+
+        // Byte offsets to UTF-16 offsets in a single pass, the merged ranges are sorted
+        let mut chars = message.char_indices().peekable();
+        let mut utf16 = 0;
+        let mut to_utf16 = |byte: usize| {
+            while let Some((_, c)) = chars.next_if(|&(i, _)| i < byte) {
+                utf16 += c.len_utf16();
+            }
+            utf16
+        };
+        merged
+            .into_iter()
+            .map(|(start, end)| (to_utf16(start), to_utf16(end)))
+            .collect()
+    }
+
+    /// Start of the time window, entries before it never match
+    pub fn time_from(&self) -> Option<DateTime<Utc>> {
+        self.time_range.from
+    }
+
+    /// Whether the record is excluded, excluded records are not even shown as context
+    pub fn excludes(&self, record: &LogRecord) -> bool {
+        self.exclusions.excludes(record)
+    }
+}
+
+/// Split a comma-separated list as used in query strings, empty items are dropped
+pub fn split_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Split a comma-separated keyword list as used in query strings. A regex can contain commas
+/// itself, e.g. `\d{1,3}`, so in regex mode the whole list is one pattern, alternatives are
+/// written with `|`.
+pub fn split_keywords(list: &str, mode: MatchMode) -> Vec<String> {
+    match mode {
+        MatchMode::Regex => Some(list.trim())
+            .filter(|pattern| !pattern.is_empty())
+            .map(str::to_string)
+            .into_iter()
+            .collect(),
+        _ => split_list(list),
+    }
+}
+
+#[cfg(test)]
+mod tests;
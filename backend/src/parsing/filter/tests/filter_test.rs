@@ -0,0 +1,154 @@
+use super::super::*;
+
+fn record(level: &str, domain: &str, message: &str) -> LogRecord {
+    LogRecord {
+        level: Some(level.to_string()),
+        domain: Some(domain.to_string()),
+        message: message.to_string(),
+        ..Default::default()
+    }
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_default_filter_matches_everything() {
+    let filter = Filter::default();
+
+    assert!(filter.matches(&record("INFO", "core", "Started")));
+    assert!(filter.matches(&LogRecord::default()));
+}
+
+#[test]
+fn test_all_conditions_must_match() {
+    let spec = FilterSpec {
+        domains: Some(strings(&["core", "network"])),
+        min_level: Some(Severity::Warn),
+        keywords: Some(strings(&["timeout", "refused"])),
+        match_mode: MatchMode::IgnoreCase,
+        query: Some("NOT retrying".to_string()),
+        exclude_domains: strings(&["network"]),
+        ..Default::default()
+    };
+    let filter = Filter::compile(&spec, None, None).unwrap();
+
+    assert!(filter.matches(&record("ERROR", "core", "Read TIMEOUT")));
+    assert!(!filter.matches(&record("INFO", "core", "Read timeout")));
+    assert!(!filter.matches(&record("ERROR", "data_acq", "Read timeout")));
+    assert!(!filter.matches(&record("ERROR", "core", "Disk full")));
+    assert!(!filter.matches(&record("ERROR", "core", "timeout, retrying")));
+    // exclusions win over the domain list
+    assert!(!filter.matches(&record("ERROR", "network", "Connection refused")));
+    assert!(filter.excludes(&record("ERROR", "network", "Connection refused")));
+}
+
+#[test]
+fn test_levels_are_normalized_and_empty_fields_ignored() {
+    let spec = FilterSpec {
+        levels: Some(strings(&["warning"])),
+        fields: strings(&["", " "]),
+        ..Default::default()
+    };
+    let filter = Filter::compile(&spec, None, None).unwrap();
+
+    assert!(filter.matches(&record("WARN", "core", "Slow")));
+    assert!(!filter.matches(&record("ERROR", "core", "Slow")));
+}
+
+#[test]
+fn test_compile_reports_invalid_options() {
+    let compile = |spec: FilterSpec| Filter::compile(&spec, None, None).unwrap_err();
+
+    let error = compile(FilterSpec {
+        query: Some("level:ERROR AND".to_string()),
+        ..Default::default()
+    });
+    assert!(matches!(error, FilterError::Query(e) if e.position == 15));
+
+    let error = compile(FilterSpec {
+        keywords: Some(strings(&["a", "b("])),
+        match_mode: MatchMode::Regex,
+        ..Default::default()
+    });
+    assert!(matches!(error, FilterError::Invalid(e) if e.starts_with("Invalid regex")));
+
+    assert!(matches!(
+        compile(FilterSpec {
+            fields: strings(&["status"]),
+            ..Default::default()
+        }),
+        FilterError::Invalid(_)
+    ));
+    assert!(matches!(
+        compile(FilterSpec {
+            from: Some("yesterday".to_string()),
+            ..Default::default()
+        }),
+        FilterError::Invalid(_)
+    ));
+}
+
+#[test]
+fn test_split_list_trims_entries() {
+    assert_eq!(split_list("a, b ,c"), strings(&["a", "b", "c"]));
+    assert_eq!(split_list("network,"), strings(&["network"]));
+    assert!(split_list(" , ").is_empty());
+    assert!(split_keywords("", MatchMode::Regex).is_empty());
+}
+
+#[test]
+fn test_regex_keywords_keep_commas() {
+    assert_eq!(
+        split_keywords(r"code \d{1,3}", MatchMode::Regex),
+        strings(&[r"code \d{1,3}"])
+    );
+    assert_eq!(
+        split_keywords("a, b", MatchMode::Literal),
+        strings(&["a", "b"])
+    );
+
+    let spec = FilterSpec {
+        keywords: Some(split_keywords(r"code \d{1,3}\b", MatchMode::Regex)),
+        match_mode: MatchMode::Regex,
+        ..Default::default()
+    };
+    let filter = Filter::compile(&spec, None, None).unwrap();
+    assert!(filter.matches(&record("ERROR", "http", "failed with code 503")));
+    assert!(!filter.matches(&record("ERROR", "http", "failed with code 5034")));
+}
+
+#[test]
+fn test_highlights_are_merged_character_ranges() {
+    let spec = FilterSpec {
+        keywords: Some(strings(&["sensor b", "b fail"])),
+        match_mode: MatchMode::IgnoreCase,
+        query: Some(r#""timeout" AND NOT "retry""#.to_string()),
+        ..Default::default()
+    };
+    let filter = Filter::compile(&spec, None, None).unwrap();
+
+    // "ö" takes two bytes but is one UTF-16 code unit
+    assert_eq!(
+        filter.highlights("Lösung: Sensor B failed, timeout"),
+        vec![(8, 21), (25, 32)]
+    );
+    assert!(filter.highlights("nothing to see").is_empty());
+    assert!(Filter::default().highlights("Sensor B").is_empty());
+}
+
+#[test]
+fn test_highlights_count_utf16_code_units() {
+    let spec = FilterSpec {
+        keywords: Some(strings(&["fire", "🔥"])),
+        ..Default::default()
+    };
+    let filter = Filter::compile(&spec, None, None).unwrap();
+
+    // "🔥" takes four bytes and two UTF-16 code units, like "🔥".length in JavaScript
+    let message = "🔥 fire é 🔥";
+    assert_eq!(filter.highlights(message), vec![(0, 2), (3, 7), (10, 12)]);
+    let utf16: Vec<u16> = message.encode_utf16().collect();
+    assert_eq!(String::from_utf16(&utf16[3..7]).unwrap(), "fire");
+}
@@ -0,0 +1 @@
+mod filter_test;
@@ -0,0 +1,86 @@
+// local
+use crate::parsing::format::bracketed::BracketedFormat;
+use crate::parsing::format::logfmt::LogfmtFormat;
+use crate::parsing::format::ndjson::{FieldMapping, NdjsonFormat};
+use crate::parsing::format::syslog::{Rfc3164Format, Rfc5424Format};
+use crate::parsing::level::normalize_level;
+use crate::parsing::record::LogRecord;
+use crate::parsing::timestamp::parse_timestamp;
+// once_cell
+use once_cell::sync::Lazy;
+// std
+use std::sync::Arc;
+
+pub mod bracketed;
+pub mod logfmt;
+pub mod ndjson;
+pub mod syslog;
+
+/// A log line format that can be turned into a structured [`LogRecord`]
+pub trait LogFormat: Send + Sync {
+    /// Unique name used to select the format, e.g. "bracketed"
+    fn name(&self) -> &'static str;
+
+    /// Parse a single line, returns `None` if the line is not in this format
+    fn parse_line(&self, line: &str) -> Option<LogRecord>;
+
+    /// Parse a single line including its timestamp, the level is normalized to its
+    /// canonical name, e.g. "warning" becomes "WARN"
+    fn parse_record(&self, line: &str) -> Option<LogRecord> {
+        let mut record = self.parse_line(line)?;
+        record.time = record.timestamp.as_deref().and_then(parse_timestamp);
+        record.level = record.level.as_deref().map(normalize_level);
+        Some(record)
+    }
+}
+
+/// Registry of all built-in formats, the first one is the default
+pub static FORMATS: Lazy<Vec<Arc<dyn LogFormat>>> = Lazy::new(|| {
+    vec![
+        Arc::new(BracketedFormat) as Arc<dyn LogFormat>,
+        Arc::new(NdjsonFormat::default()),
+        Arc::new(Rfc5424Format),
+        Arc::new(Rfc3164Format),
+        Arc::new(LogfmtFormat),
+    ]
+});
+
+/// Look up a built-in format by its name
+pub fn find_format(name: &str) -> Option<Arc<dyn LogFormat>> {
+    FORMATS
+        .iter()
+        .find(|format| format.name().eq_ignore_ascii_case(name))
+        .cloned()
+}
+
+/// Format used when none is specified
+pub fn default_format() -> Arc<dyn LogFormat> {
+    FORMATS[0].clone()
+}
+
+/// Resolve an optional format name, falling back to the default format.
+/// A field mapping selects the NDJSON format configured with that mapping.
+pub fn resolve_format(
+    name: Option<&str>,
+    field_mapping: Option<&FieldMapping>,
+) -> Result<Arc<dyn LogFormat>, String> {
+    let format = match name {
+        Some(name) => find_format(name).ok_or_else(|| format!("Unknown log format: {}", name))?,
+        None if field_mapping.is_some() => return resolve_format(Some("ndjson"), field_mapping),
+        None => default_format(),
+    };
+
+    match field_mapping {
+        Some(mapping) if format.name() == "ndjson" => {
+            Ok(Arc::new(NdjsonFormat::new(mapping.clone())))
+        }
+        Some(_) => Err(format!(
+            "A field mapping is not supported by the {} format",
+            format.name()
+        )),
+        None => Ok(format),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,34 @@
+// local
+use crate::parsing::format::LogFormat;
+use crate::parsing::record::LogRecord;
+// once_cell
+use once_cell::sync::Lazy;
+// regex
+use regex::Regex;
+
+static BRACKETED_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\[(?P<ts>[^\]]+)\]\s\[(?P<level>[^\]]+)\]\s\[(?P<domain>[^\]]+)\]\s*(?P<message>.*)$",
+    )
+    .unwrap()
+});
+
+/// Lines in the form `[ts] [level] [domain] message`
+pub struct BracketedFormat;
+
+impl LogFormat for BracketedFormat {
+    fn name(&self) -> &'static str {
+        "bracketed"
+    }
+
+    fn parse_line(&self, line: &str) -> Option<LogRecord> {
+        let caps = BRACKETED_REGEX.captures(line)?;
+        Some(LogRecord {
+            timestamp: Some(caps["ts"].to_string()),
+            level: Some(caps["level"].to_string()),
+            domain: Some(caps["domain"].to_string()),
+            message: caps["message"].to_string(),
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,140 @@
+// local
+use crate::parsing::format::LogFormat;
+use crate::parsing::record::{FieldValue, LogRecord};
+// std
+use std::collections::BTreeMap;
+
+const TIMESTAMP_KEYS: [&str; 4] = ["time", "ts", "t", "timestamp"];
+const LEVEL_KEYS: [&str; 3] = ["level", "lvl", "severity"];
+const DOMAIN_KEYS: [&str; 4] = ["component", "logger", "module", "target"];
+const MESSAGE_KEYS: [&str; 2] = ["msg", "message"];
+
+/// logfmt, `time=... level=warn component=net msg="..." dur=12ms`. Every key that is not a
+/// well-known part of the record is kept as a typed field.
+pub struct LogfmtFormat;
+
+impl LogFormat for LogfmtFormat {
+    fn name(&self) -> &'static str {
+        "logfmt"
+    }
+
+    fn parse_line(&self, line: &str) -> Option<LogRecord> {
+        let pairs = parse_pairs(line)?;
+
+        // require at least one well-known key, so that plain text is not taken for logfmt
+        let well_known = [
+            &TIMESTAMP_KEYS[..],
+            &LEVEL_KEYS[..],
+            &DOMAIN_KEYS[..],
+            &MESSAGE_KEYS[..],
+        ];
+        if !pairs
+            .iter()
+            .any(|(key, value)| value.is_some() && well_known.iter().any(|k| k.contains(key)))
+        {
+            return None;
+        }
+
+        let mut record = LogRecord::default();
+        let mut fields = BTreeMap::new();
+        for (key, value) in pairs {
+            // bare keys are flags
+            let Some(value) = value else {
+                fields.insert(key.to_string(), FieldValue::Bool(true));
+                continue;
+            };
+
+            let slot = if TIMESTAMP_KEYS.contains(&key) {
+                &mut record.timestamp
+            } else if LEVEL_KEYS.contains(&key) {
+                &mut record.level
+            } else if DOMAIN_KEYS.contains(&key) {
+                &mut record.domain
+            } else if MESSAGE_KEYS.contains(&key) && record.message.is_empty() {
+                record.message = value;
+                continue;
+            } else {
+                fields.insert(key.to_string(), FieldValue::infer(&value));
+                continue;
+            };
+
+            if slot.is_none() {
+                *slot = Some(value);
+            } else {
+                fields.insert(key.to_string(), FieldValue::String(value));
+            }
+        }
+        record.fields = fields;
+
+        Some(record)
+    }
+}
+
+/// Split a line into `key=value` pairs, values may be quoted. Returns `None` if the line is not
+/// valid logfmt or has no `key=value` pair at all.
+fn parse_pairs(line: &str) -> Option<Vec<(&str, Option<String>)>> {
+    let mut pairs = Vec::new();
+    let mut rest = line.trim();
+
+    while !rest.is_empty() {
+        let key_end = rest.find(['=', ' ', '"']).unwrap_or(rest.len());
+        let key = &rest[..key_end];
+        if key.is_empty() || !key.chars().all(is_key_char) {
+            return None;
+        }
+        rest = &rest[key_end..];
+
+        match rest.strip_prefix('=') {
+            Some(after) => {
+                let (value, after) = match after.strip_prefix('"') {
+                    Some(quoted) => parse_quoted(quoted)?,
+                    None => {
+                        let end = after.find(' ').unwrap_or(after.len());
+                        let value = &after[..end];
+                        if value.contains(['"', '=']) {
+                            return None;
+                        }
+                        (value.to_string(), &after[end..])
+                    }
+                };
+                pairs.push((key, Some(value)));
+                rest = after;
+            }
+            None if rest.starts_with('"') => return None,
+            None => pairs.push((key, None)),
+        }
+
+        // pairs are separated by whitespace
+        if !rest.is_empty() && !rest.starts_with(' ') {
+            return None;
+        }
+        rest = rest.trim_start();
+    }
+
+    pairs
+        .iter()
+        .any(|(_, value)| value.is_some())
+        .then_some(pairs)
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '-' | '@' | '/' | ':')
+}
+
+/// Read a quoted value up to the closing quote, resolving backslash escapes
+fn parse_quoted(input: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = input.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &input[i + 1..])),
+            '\\' => match chars.next()? {
+                (_, 'n') => value.push('\n'),
+                (_, 't') => value.push('\t'),
+                (_, escaped) => value.push(escaped),
+            },
+            _ => value.push(c),
+        }
+    }
+    None
+}
@@ -0,0 +1,115 @@
+// local
+use crate::parsing::format::LogFormat;
+use crate::parsing::record::{FieldValue, LogRecord};
+// serde
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+// utoipa
+use utoipa::ToSchema;
+// std
+use std::collections::BTreeMap;
+
+/// Which JSON keys hold the well-known parts of a record. For each part the first key present
+/// in the object is used. Nested objects are flattened, so `log.level` addresses
+/// `{"log": {"level": ...}}`.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, ToSchema)]
+#[serde(default)]
+pub struct FieldMapping {
+    /// Candidate keys for the timestamp
+    pub timestamp: Vec<String>,
+    /// Candidate keys for the log level
+    pub level: Vec<String>,
+    /// Candidate keys for the domain
+    pub domain: Vec<String>,
+    /// Candidate keys for the message
+    pub message: Vec<String>,
+}
+
+impl Default for FieldMapping {
+    fn default() -> Self {
+        let keys = |keys: &[&str]| keys.iter().map(|k| k.to_string()).collect();
+        Self {
+            timestamp: keys(&["ts", "time", "timestamp", "@timestamp"]),
+            level: keys(&["level", "severity", "lvl", "log.level"]),
+            domain: keys(&["logger", "target", "component", "logger_name"]),
+            message: keys(&["msg", "message"]),
+        }
+    }
+}
+
+/// JSON Lines, one JSON object per line
+#[derive(Default)]
+pub struct NdjsonFormat {
+    mapping: FieldMapping,
+}
+
+impl NdjsonFormat {
+    pub fn new(mapping: FieldMapping) -> Self {
+        Self { mapping }
+    }
+}
+
+impl LogFormat for NdjsonFormat {
+    fn name(&self) -> &'static str {
+        "ndjson"
+    }
+
+    fn parse_line(&self, line: &str) -> Option<LogRecord> {
+        let line = line.trim();
+        if !line.starts_with('{') {
+            return None;
+        }
+        let object: Map<String, Value> = serde_json::from_str(line).ok()?;
+
+        let mut fields = BTreeMap::new();
+        flatten("", object, &mut fields);
+
+        let mut take = |keys: &[String]| {
+            keys.iter()
+                .find_map(|key| fields.remove(key))
+                .map(|value| value.to_string())
+        };
+        let timestamp = take(&self.mapping.timestamp);
+        let level = take(&self.mapping.level);
+        let domain = take(&self.mapping.domain);
+        let message = take(&self.mapping.message).unwrap_or_default();
+
+        Some(LogRecord {
+            timestamp,
+            level,
+            domain,
+            message,
+            fields,
+            ..Default::default()
+        })
+    }
+}
+
+/// Flatten nested objects into dotted keys, arrays are kept as JSON text and nulls are dropped
+fn flatten(prefix: &str, object: Map<String, Value>, fields: &mut BTreeMap<String, FieldValue>) {
+    for (key, value) in object {
+        let key = if prefix.is_empty() {
+            key
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            Value::Null => {}
+            Value::Bool(b) => {
+                fields.insert(key, FieldValue::Bool(b));
+            }
+            Value::Number(n) => {
+                if let Some(n) = n.as_f64() {
+                    fields.insert(key, FieldValue::Number(n));
+                }
+            }
+            Value::String(s) => {
+                fields.insert(key, FieldValue::String(s));
+            }
+            Value::Array(_) => {
+                fields.insert(key, FieldValue::String(value.to_string()));
+            }
+            Value::Object(nested) => flatten(&key, nested, fields),
+        }
+    }
+}
@@ -0,0 +1,201 @@
+// local
+use crate::parsing::format::LogFormat;
+use crate::parsing::record::{FieldValue, LogRecord};
+// once_cell
+use once_cell::sync::Lazy;
+// regex
+use regex::Regex;
+// std
+use std::collections::BTreeMap;
+
+/// Field holding the syslog facility, e.g. "auth"
+pub const FACILITY_FIELD: &str = "facility";
+/// Field holding the host name the message originates from
+pub const HOST_FIELD: &str = "host";
+
+const SEVERITIES: [&str; 8] = [
+    "EMERG", "ALERT", "CRIT", "ERR", "WARNING", "NOTICE", "INFO", "DEBUG",
+];
+
+const FACILITIES: [&str; 24] = [
+    "kern",
+    "user",
+    "mail",
+    "daemon",
+    "auth",
+    "syslog",
+    "lpr",
+    "news",
+    "uucp",
+    "cron",
+    "authpriv",
+    "ftp",
+    "ntp",
+    "security",
+    "console",
+    "solaris-cron",
+    "local0",
+    "local1",
+    "local2",
+    "local3",
+    "local4",
+    "local5",
+    "local6",
+    "local7",
+];
+
+static RFC3164_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^(?:<(?P<pri>\d{1,3})>)?(?P<ts>[A-Z][a-z]{2} [ \d]\d \d{2}:\d{2}:\d{2}) (?P<host>\S+) (?P<app>[^:\[\s]+)(?:\[(?P<procid>[^\]]*)\])?: ?(?P<message>.*)$",
+    )
+    .unwrap()
+});
+
+static RFC5424_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^<(?P<pri>\d{1,3})>\d{1,2} (?P<ts>\S+) (?P<host>\S+) (?P<app>\S+) (?P<procid>\S+) (?P<msgid>\S+) ?(?P<rest>.*)$",
+    )
+    .unwrap()
+});
+
+/// Classic BSD syslog, `<PRI>Mmm dd hh:mm:ss host app[pid]: message`. The PRI part is optional
+/// as it is usually missing in files written by syslog daemons.
+pub struct Rfc3164Format;
+
+impl LogFormat for Rfc3164Format {
+    fn name(&self) -> &'static str {
+        "rfc3164"
+    }
+
+    fn parse_line(&self, line: &str) -> Option<LogRecord> {
+        let caps = RFC3164_REGEX.captures(line)?;
+        let mut record = LogRecord {
+            timestamp: Some(caps["ts"].to_string()),
+            domain: Some(caps["app"].to_string()),
+            message: caps["message"].to_string(),
+            ..Default::default()
+        };
+        if let Some(pri) = caps.name("pri") {
+            apply_pri(&mut record, pri.as_str())?;
+        }
+        insert(&mut record.fields, HOST_FIELD, &caps["host"]);
+        if let Some(procid) = caps.name("procid") {
+            insert(&mut record.fields, "procid", procid.as_str());
+        }
+        Some(record)
+    }
+}
+
+/// Syslog as specified in RFC 5424,
+/// `<PRI>1 timestamp host app-name procid msgid [structured-data] message`
+pub struct Rfc5424Format;
+
+impl LogFormat for Rfc5424Format {
+    fn name(&self) -> &'static str {
+        "rfc5424"
+    }
+
+    fn parse_line(&self, line: &str) -> Option<LogRecord> {
+        let caps = RFC5424_REGEX.captures(line)?;
+        let mut record = LogRecord {
+            timestamp: nil_or(&caps["ts"]),
+            ..Default::default()
+        };
+        apply_pri(&mut record, &caps["pri"])?;
+
+        let host = nil_or(&caps["host"]);
+        let app = nil_or(&caps["app"]);
+        // the app name is the most useful domain, fall back to the host if it is missing
+        record.domain = app.clone().or_else(|| host.clone());
+        for (key, value) in [
+            (HOST_FIELD, host),
+            ("app", app),
+            ("procid", nil_or(&caps["procid"])),
+            ("msgid", nil_or(&caps["msgid"])),
+        ] {
+            if let Some(value) = value {
+                insert(&mut record.fields, key, &value);
+            }
+        }
+
+        let rest = &caps["rest"];
+        let message = if let Some(message) = rest.strip_prefix('-') {
+            message
+        } else if rest.starts_with('[') {
+            parse_structured_data(rest, &mut record.fields)?
+        } else {
+            return None;
+        };
+        record.message = message
+            .strip_prefix(' ')
+            .unwrap_or(message)
+            .trim_start_matches('\u{feff}')
+            .to_string();
+
+        Some(record)
+    }
+}
+
+/// Split PRI into severity (as level) and facility
+fn apply_pri(record: &mut LogRecord, pri: &str) -> Option<()> {
+    let pri: usize = pri.parse().ok()?;
+    let facility = FACILITIES.get(pri / 8)?;
+    record.level = Some(SEVERITIES[pri % 8].to_string());
+    insert(&mut record.fields, FACILITY_FIELD, facility);
+    Some(())
+}
+
+fn nil_or(value: &str) -> Option<String> {
+    (value != "-").then(|| value.to_string())
+}
+
+fn insert(fields: &mut BTreeMap<String, FieldValue>, key: &str, value: &str) {
+    fields.insert(key.to_string(), FieldValue::String(value.to_string()));
+}
+
+/// Parse structured data elements like `[id key="value" ...][id2 ...]` into fields named
+/// `id.key`, returns the remaining message
+fn parse_structured_data<'a>(
+    mut rest: &'a str,
+    fields: &mut BTreeMap<String, FieldValue>,
+) -> Option<&'a str> {
+    while let Some(element) = rest.strip_prefix('[') {
+        let id_end = element.find([' ', ']'])?;
+        let id = &element[..id_end];
+        rest = &element[id_end..];
+
+        loop {
+            rest = rest.trim_start_matches(' ');
+            if let Some(after) = rest.strip_prefix(']') {
+                rest = after;
+                break;
+            }
+            let (name, after) = rest.split_once("=\"")?;
+            let (value, after) = parse_param_value(after)?;
+            insert(fields, &format!("{}.{}", id, name), &value);
+            rest = after;
+        }
+    }
+    Some(rest)
+}
+
+/// Read a quoted parameter value up to the closing quote, resolving `\"`, `\\` and `\]` escapes
+fn parse_param_value(input: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = input.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &input[i + 1..])),
+            '\\' => match chars.next() {
+                Some((_, escaped @ ('"' | '\\' | ']'))) => value.push(escaped),
+                Some((_, other)) => {
+                    value.push('\\');
+                    value.push(other);
+                }
+                None => return None,
+            },
+            _ => value.push(c),
+        }
+    }
+    None
+}
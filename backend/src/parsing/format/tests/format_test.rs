@@ -0,0 +1,202 @@
+use super::super::*;
+use crate::parsing::record::FieldValue;
+
+#[test]
+fn test_bracketed_parses_all_parts() {
+    let format = find_format("bracketed").unwrap();
+
+    let record = format
+        .parse_line("[2025-11-17 12:50:00.763190172] [DEBUG] [network] Data received")
+        .unwrap();
+
+    assert_eq!(
+        record.timestamp.as_deref(),
+        Some("2025-11-17 12:50:00.763190172")
+    );
+    assert_eq!(record.level.as_deref(), Some("DEBUG"));
+    assert_eq!(record.domain.as_deref(), Some("network"));
+    assert_eq!(record.message, "Data received");
+}
+
+#[test]
+fn test_bracketed_rejects_other_lines() {
+    let format = default_format();
+
+    assert!(format.parse_line("thread 'main' panicked").is_none());
+    assert!(format.parse_line("").is_none());
+}
+
+#[test]
+fn test_resolve_unknown_format_fails() {
+    assert!(resolve_format(None, None).is_ok());
+    assert!(resolve_format(Some("BRACKETED"), None).is_ok());
+    assert!(resolve_format(Some("does-not-exist"), None).is_err());
+    assert!(resolve_format(Some("bracketed"), Some(&FieldMapping::default())).is_err());
+}
+
+#[test]
+fn test_ndjson_uses_default_mapping() {
+    let format = find_format("ndjson").unwrap();
+
+    let record = format
+        .parse_line(r#"{"time":"2025-11-17T12:50:00Z","severity":"warn","target":"net","msg":"slow","dur":12,"http":{"status":503}}"#)
+        .unwrap();
+
+    assert_eq!(record.timestamp.as_deref(), Some("2025-11-17T12:50:00Z"));
+    assert_eq!(record.level.as_deref(), Some("warn"));
+    assert_eq!(record.domain.as_deref(), Some("net"));
+    assert_eq!(record.message, "slow");
+    assert_eq!(record.fields.get("dur"), Some(&FieldValue::Number(12.0)));
+    assert_eq!(
+        record.fields.get("http.status"),
+        Some(&FieldValue::Number(503.0))
+    );
+    assert!(format
+        .parse_line("[2025-11-17] [INFO] [core] not json")
+        .is_none());
+}
+
+#[test]
+fn test_ndjson_uses_custom_mapping() {
+    let mapping: FieldMapping =
+        serde_json::from_str(r#"{"level": ["sev"], "domain": ["service.name"]}"#).unwrap();
+    let format = resolve_format(None, Some(&mapping)).unwrap();
+
+    let record = format
+        .parse_line(r#"{"sev":"ERROR","level":"ignored","service":{"name":"api"},"msg":"boom"}"#)
+        .unwrap();
+
+    assert_eq!(format.name(), "ndjson");
+    assert_eq!(record.level.as_deref(), Some("ERROR"));
+    assert_eq!(record.domain.as_deref(), Some("api"));
+    assert_eq!(record.message, "boom");
+    assert_eq!(
+        record.fields.get("level"),
+        Some(&FieldValue::String("ignored".to_string()))
+    );
+}
+
+#[test]
+fn test_rfc3164_maps_pri_host_and_app() {
+    let format = find_format("rfc3164").unwrap();
+
+    let record = format
+        .parse_line(
+            "<34>Oct 11 22:14:15 mymachine su[230]: 'su root' failed for lonvick on /dev/pts/8",
+        )
+        .unwrap();
+
+    assert_eq!(record.timestamp.as_deref(), Some("Oct 11 22:14:15"));
+    assert_eq!(record.level.as_deref(), Some("CRIT"));
+    assert_eq!(record.domain.as_deref(), Some("su"));
+    assert_eq!(record.message, "'su root' failed for lonvick on /dev/pts/8");
+    assert_eq!(record.fields.get("facility").unwrap().to_string(), "auth");
+    assert_eq!(record.fields.get("host").unwrap().to_string(), "mymachine");
+    assert_eq!(record.fields.get("procid").unwrap().to_string(), "230");
+
+    // files written by syslog daemons have no PRI
+    let record = format
+        .parse_line("Nov  7 08:01:02 raspberrypi CRON: (root) CMD (run-parts)")
+        .unwrap();
+    assert_eq!(record.level, None);
+    assert_eq!(record.domain.as_deref(), Some("CRON"));
+}
+
+#[test]
+fn test_rfc5424_parses_structured_data() {
+    let format = find_format("rfc5424").unwrap();
+
+    let record = format
+        .parse_line(r#"<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog - ID47 [exampleSDID@32473 iut="3" eventSource="App \"x\""][origin ip="10.0.0.1"] An application event"#)
+        .unwrap();
+
+    assert_eq!(
+        record.timestamp.as_deref(),
+        Some("2003-10-11T22:14:15.003Z")
+    );
+    assert_eq!(record.level.as_deref(), Some("NOTICE"));
+    assert_eq!(record.domain.as_deref(), Some("evntslog"));
+    assert_eq!(record.message, "An application event");
+    assert_eq!(record.fields.get("facility").unwrap().to_string(), "local4");
+    assert_eq!(record.fields.get("msgid").unwrap().to_string(), "ID47");
+    assert!(!record.fields.contains_key("procid"));
+    assert_eq!(
+        record
+            .fields
+            .get("exampleSDID@32473.iut")
+            .unwrap()
+            .to_string(),
+        "3"
+    );
+    assert_eq!(
+        record
+            .fields
+            .get("exampleSDID@32473.eventSource")
+            .unwrap()
+            .to_string(),
+        "App \"x\""
+    );
+    assert_eq!(
+        record.fields.get("origin.ip").unwrap().to_string(),
+        "10.0.0.1"
+    );
+
+    let record = format
+        .parse_line("<11>1 - - - - - - no header values")
+        .unwrap();
+    assert_eq!(record.level.as_deref(), Some("ERR"));
+    assert_eq!(record.domain, None);
+    assert_eq!(record.message, "no header values");
+}
+
+#[test]
+fn test_logfmt_maps_well_known_keys_and_types_fields() {
+    let format = find_format("logfmt").unwrap();
+
+    let record = format
+        .parse_line(r#"time=2025-11-17T12:50:00Z level=warn component=net msg="slow \"peer\" response" dur=12ms status=503 retry=true cached"#)
+        .unwrap();
+
+    assert_eq!(record.timestamp.as_deref(), Some("2025-11-17T12:50:00Z"));
+    assert_eq!(record.level.as_deref(), Some("warn"));
+    assert_eq!(record.domain.as_deref(), Some("net"));
+    assert_eq!(record.message, "slow \"peer\" response");
+    assert_eq!(
+        record.fields.get("dur"),
+        Some(&FieldValue::String("12ms".to_string()))
+    );
+    assert_eq!(
+        record.fields.get("status"),
+        Some(&FieldValue::Number(503.0))
+    );
+    assert_eq!(record.fields.get("retry"), Some(&FieldValue::Bool(true)));
+    assert_eq!(record.fields.get("cached"), Some(&FieldValue::Bool(true)));
+}
+
+#[test]
+fn test_logfmt_rejects_plain_text() {
+    let format = find_format("logfmt").unwrap();
+
+    assert!(format.parse_line("just some words").is_none());
+    assert!(format.parse_line("a=1 b=2").is_none());
+    assert!(format.parse_line(r#"msg="unterminated"#).is_none());
+    assert!(format
+        .parse_line("[2025-11-17 12:50:00] [INFO] [core] x=1 msg=hi")
+        .is_none());
+}
+
+#[test]
+fn test_parse_record_normalizes_level() {
+    let format = find_format("rfc3164").unwrap();
+
+    let record = format
+        .parse_record("<34>Oct 11 22:14:15 mymachine su[230]: 'su root' failed")
+        .unwrap();
+    assert_eq!(record.level.as_deref(), Some("FATAL"));
+
+    let record = find_format("logfmt")
+        .unwrap()
+        .parse_record("level=warning msg=slow")
+        .unwrap();
+    assert_eq!(record.level.as_deref(), Some("WARN"));
+}
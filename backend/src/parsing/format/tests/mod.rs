@@ -0,0 +1 @@
+mod format_test;
@@ -0,0 +1,90 @@
+// regex
+use regex::{Regex, RegexBuilder};
+// serde
+use serde::Deserialize;
+// utoipa
+use utoipa::ToSchema;
+
+/// How keywords are matched against messages
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchMode {
+    /// Case-sensitive substring
+    #[default]
+    Literal,
+    /// Case-insensitive substring
+    IgnoreCase,
+    /// Regular expression
+    Regex,
+    /// Case-sensitive whole word
+    Word,
+}
+
+/// A keyword compiled for a [`MatchMode`]
+#[derive(Clone, Debug)]
+pub enum KeywordMatcher {
+    Literal(String),
+    Pattern(Regex),
+}
+
+impl KeywordMatcher {
+    /// Compile a keyword, fails with the regex error for invalid patterns in regex mode
+    pub fn new(keyword: &str, mode: MatchMode) -> Result<Self, String> {
+        let pattern = match mode {
+            MatchMode::Literal => return Ok(KeywordMatcher::Literal(keyword.to_string())),
+            MatchMode::IgnoreCase => regex::escape(keyword),
+            MatchMode::Regex => keyword.to_string(),
+            MatchMode::Word => {
+                // only require a word boundary where the keyword itself starts or ends with a
+                // word character, otherwise e.g. "-v" could never match
+                let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+                format!(
+                    "{}{}{}",
+                    if is_word(keyword.chars().next()) {
+                        r"\b"
+                    } else {
+                        ""
+                    },
+                    regex::escape(keyword),
+                    if is_word(keyword.chars().last()) {
+                        r"\b"
+                    } else {
+                        ""
+                    },
+                )
+            }
+        };
+
+        RegexBuilder::new(&pattern)
+            .case_insensitive(mode == MatchMode::IgnoreCase)
+            .build()
+            .map(KeywordMatcher::Pattern)
+            .map_err(|e| format!("Invalid regex '{}': {}", keyword, e))
+    }
+
+    /// Byte ranges of all non-empty hits in the text
+    pub fn find_all(&self, text: &str) -> Vec<(usize, usize)> {
+        match self {
+            KeywordMatcher::Literal(keyword) if keyword.is_empty() => Vec::new(),
+            KeywordMatcher::Literal(keyword) => text
+                .match_indices(keyword.as_str())
+                .map(|(start, hit)| (start, start + hit.len()))
+                .collect(),
+            KeywordMatcher::Pattern(regex) => regex
+                .find_iter(text)
+                .filter(|hit| !hit.is_empty())
+                .map(|hit| (hit.start(), hit.end()))
+                .collect(),
+        }
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        match self {
+            KeywordMatcher::Literal(keyword) => text.contains(keyword.as_str()),
+            KeywordMatcher::Pattern(regex) => regex.is_match(text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,60 @@
+use super::super::*;
+
+fn matches(keyword: &str, mode: MatchMode, text: &str) -> bool {
+    KeywordMatcher::new(keyword, mode).unwrap().is_match(text)
+}
+
+#[test]
+fn test_literal_and_ignore_case() {
+    assert!(matches("Timeout", MatchMode::Literal, "I/O Timeout"));
+    assert!(!matches("timeout", MatchMode::Literal, "I/O Timeout"));
+    assert!(matches("timeout", MatchMode::IgnoreCase, "I/O TIMEOUT"));
+    // special characters are taken literally
+    assert!(matches("(I/O", MatchMode::IgnoreCase, "read (i/o timeout)"));
+}
+
+#[test]
+fn test_regex_and_whole_word() {
+    assert!(matches(
+        r"sensor [A-C]\b",
+        MatchMode::Regex,
+        "read sensor B failed"
+    ));
+    assert!(!matches(
+        r"^sensor",
+        MatchMode::Regex,
+        "read sensor B failed"
+    ));
+
+    assert!(matches("error", MatchMode::Word, "an error occurred"));
+    assert!(!matches("error", MatchMode::Word, "no errors"));
+    assert!(matches("-v", MatchMode::Word, "started with -v flag"));
+}
+
+#[test]
+fn test_invalid_regex_is_rejected() {
+    let error = KeywordMatcher::new("sensor (", MatchMode::Regex).unwrap_err();
+
+    assert!(error.starts_with("Invalid regex 'sensor ('"), "{}", error);
+    assert!(KeywordMatcher::new("b(", MatchMode::Literal).is_ok());
+}
+
+#[test]
+fn test_find_all_returns_every_hit() {
+    let find = |keyword: &str, mode: MatchMode, text: &str| {
+        KeywordMatcher::new(keyword, mode).unwrap().find_all(text)
+    };
+
+    assert_eq!(
+        find("ab", MatchMode::Literal, "ab xab ab"),
+        vec![(0, 2), (4, 6), (7, 9)]
+    );
+    assert_eq!(find("AB", MatchMode::IgnoreCase, "xab"), vec![(1, 3)]);
+    assert_eq!(
+        find(r"\d+", MatchMode::Regex, "a1 b22"),
+        vec![(1, 2), (4, 6)]
+    );
+    // empty hits are not reported
+    assert!(find("", MatchMode::Literal, "abc").is_empty());
+    assert!(find("x*", MatchMode::Regex, "abc").is_empty());
+}
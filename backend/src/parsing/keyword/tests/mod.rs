@@ -0,0 +1 @@
+mod keyword_test;
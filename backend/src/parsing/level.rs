@@ -0,0 +1,99 @@
+// serde
+use serde::Deserialize;
+// utoipa
+use utoipa::ToSchema;
+
+/// Canonical severity scale, ordered from least to most severe
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, ToSchema)]
+#[serde(try_from = "String", rename_all = "UPPERCASE")]
+pub enum Severity {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl Severity {
+    /// Normalize a level as found in a log, e.g. "warning", "W", "err", "CRIT", syslog
+    /// severities (0-7) or numeric levels as used by pino/bunyan (10-60)
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        if let Ok(number) = raw.parse::<u32>() {
+            return Self::from_number(number);
+        }
+
+        Some(match raw.to_ascii_lowercase().as_str() {
+            "trace" | "trc" | "t" | "verbose" | "v" | "finest" | "finer" => Severity::Trace,
+            "debug" | "dbg" | "d" | "fine" | "config" => Severity::Debug,
+            "info" | "inf" | "i" | "information" | "informational" | "notice" | "n" => {
+                Severity::Info
+            }
+            "warn" | "warning" | "wrn" | "w" => Severity::Warn,
+            "error" | "err" | "e" | "severe" => Severity::Error,
+            "fatal" | "ftl" | "f" | "crit" | "critical" | "c" | "alert" | "emerg" | "emergency"
+            | "panic" => Severity::Fatal,
+            _ => return None,
+        })
+    }
+
+    /// Syslog severities (0 = emergency ... 7 = debug) and pino/bunyan levels (10 ... 60)
+    fn from_number(number: u32) -> Option<Self> {
+        Some(match number {
+            0..=2 => Severity::Fatal,
+            3 => Severity::Error,
+            4 => Severity::Warn,
+            5 | 6 => Severity::Info,
+            7 => Severity::Debug,
+            10 => Severity::Trace,
+            20 => Severity::Debug,
+            30 => Severity::Info,
+            40 => Severity::Warn,
+            50 => Severity::Error,
+            60 => Severity::Fatal,
+            _ => return None,
+        })
+    }
+
+    /// Canonical name of the severity, e.g. "WARN"
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Trace => "TRACE",
+            Severity::Debug => "DEBUG",
+            Severity::Info => "INFO",
+            Severity::Warn => "WARN",
+            Severity::Error => "ERROR",
+            Severity::Fatal => "FATAL",
+        }
+    }
+}
+
+impl TryFrom<String> for Severity {
+    type Error = String;
+
+    fn try_from(raw: String) -> Result<Self, String> {
+        Severity::parse(&raw).ok_or_else(|| format!("Unknown log level: {}", raw))
+    }
+}
+
+/// Normalize a level to its canonical name, unknown levels are kept as they are
+pub fn normalize_level(raw: &str) -> String {
+    Severity::parse(raw)
+        .map(|severity| severity.as_str().to_string())
+        .unwrap_or_else(|| raw.to_string())
+}
+
+/// Whether a level is at least as severe as `min`, unknown levels never are
+pub fn is_at_least(level: &str, min: Severity) -> bool {
+    Severity::parse(level).is_some_and(|severity| severity >= min)
+}
+
+/// Sort key putting canonical levels in severity order, followed by unknown levels by name
+pub fn level_order(level: &str) -> (bool, Option<Severity>, &str) {
+    let severity = Severity::parse(level);
+    (severity.is_none(), severity, level)
+}
+
+#[cfg(test)]
+mod tests;
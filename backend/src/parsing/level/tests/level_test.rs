@@ -0,0 +1,46 @@
+use super::super::*;
+
+#[test]
+fn test_parse_normalizes_aliases() {
+    assert_eq!(Severity::parse("warning"), Some(Severity::Warn));
+    assert_eq!(Severity::parse("W"), Some(Severity::Warn));
+    assert_eq!(Severity::parse("err"), Some(Severity::Error));
+    assert_eq!(Severity::parse("CRIT"), Some(Severity::Fatal));
+    assert_eq!(Severity::parse(" Info "), Some(Severity::Info));
+    assert_eq!(Severity::parse("NOTICE"), Some(Severity::Info));
+    assert_eq!(Severity::parse("custom"), None);
+}
+
+#[test]
+fn test_parse_numeric_severities() {
+    // syslog
+    assert_eq!(Severity::parse("0"), Some(Severity::Fatal));
+    assert_eq!(Severity::parse("3"), Some(Severity::Error));
+    assert_eq!(Severity::parse("4"), Some(Severity::Warn));
+    assert_eq!(Severity::parse("7"), Some(Severity::Debug));
+    // pino / bunyan
+    assert_eq!(Severity::parse("30"), Some(Severity::Info));
+    assert_eq!(Severity::parse("60"), Some(Severity::Fatal));
+    assert_eq!(Severity::parse("42"), None);
+}
+
+#[test]
+fn test_ordering_and_threshold() {
+    assert!(Severity::Trace < Severity::Debug);
+    assert!(Severity::Error < Severity::Fatal);
+
+    assert!(is_at_least("ERROR", Severity::Warn));
+    assert!(is_at_least("warning", Severity::Warn));
+    assert!(!is_at_least("INFO", Severity::Warn));
+    assert!(!is_at_least("custom", Severity::Trace));
+
+    let mut levels = vec!["custom", "ERROR", "DEBUG", "another", "WARN"];
+    levels.sort_by_key(|level| level_order(level));
+    assert_eq!(levels, vec!["DEBUG", "WARN", "ERROR", "another", "custom"]);
+}
+
+#[test]
+fn test_normalize_keeps_unknown_levels() {
+    assert_eq!(normalize_level("wrn"), "WARN");
+    assert_eq!(normalize_level("custom"), "custom");
+}
@@ -0,0 +1,132 @@
+// chrono
+use chrono::{DateTime, Utc};
+// serde
+use serde::{Deserialize, Serialize};
+
+/// Number of lines per block of a [`LineIndex`]
+pub const BLOCK_LINES: usize = 1024;
+
+/// Start of a block of lines
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexBlock {
+    /// Line number of the first line of the block (1-based)
+    pub line_number: usize,
+    /// Byte offset of the first line of the block
+    pub byte_offset: u64,
+    /// Latest parsed timestamp of all entries starting in this or an earlier block. Never
+    /// decreases, so all entries before a block with `max_time < t` are before `t` as well.
+    pub max_time: Option<DateTime<Utc>>,
+}
+
+/// Compact index of a log file for random access: the start of every `block_lines`-th line
+/// and the latest timestamp seen up to each of these blocks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LineIndex {
+    pub block_lines: usize,
+    pub blocks: Vec<IndexBlock>,
+    /// Number of lines in the file
+    pub total_lines: usize,
+    /// Size of the file in bytes
+    pub total_bytes: u64,
+}
+
+impl LineIndex {
+    /// Block containing the byte offset, i.e. where to start reading to get to it
+    pub fn locate_offset(&self, byte_offset: u64) -> Option<&IndexBlock> {
+        let block = self
+            .blocks
+            .partition_point(|block| block.byte_offset <= byte_offset);
+        self.blocks.get(block.checked_sub(1)?)
+    }
+
+    /// Block to start reading from to find the first entry at or after `time`. Every entry in
+    /// earlier blocks has an earlier timestamp (or none). `None` if no entry is that late.
+    pub fn locate_time(&self, time: DateTime<Utc>) -> Option<&IndexBlock> {
+        let block = self
+            .blocks
+            .partition_point(|block| block.max_time.is_none_or(|max| max < time));
+        self.blocks.get(block)
+    }
+
+    /// Record the timestamp of an entry starting at the given line. Call [`Self::seal`] once all
+    /// entries are recorded.
+    pub fn record_time(&mut self, line_number: usize, time: DateTime<Utc>) {
+        let Some(block) = line_number
+            .checked_sub(1)
+            .and_then(|line| self.blocks.get_mut(line / self.block_lines))
+        else {
+            return;
+        };
+        if block.max_time.is_none_or(|max| max < time) {
+            block.max_time = Some(time);
+        }
+    }
+
+    /// Turn the latest timestamp per block into the running maximum [`Self::locate_time`]
+    /// relies on
+    pub fn seal(&mut self) {
+        let mut max_time: Option<DateTime<Utc>> = None;
+        for block in &mut self.blocks {
+            max_time = max_time.max(block.max_time);
+            block.max_time = max_time;
+        }
+    }
+}
+
+/// Builds the line offsets of a [`LineIndex`] from the raw bytes of a file as they are written
+#[derive(Debug)]
+pub struct LineIndexBuilder {
+    index: LineIndex,
+    /// Whether the next byte starts a new line
+    at_line_start: bool,
+}
+
+impl LineIndexBuilder {
+    pub fn new(block_lines: usize) -> Self {
+        Self {
+            index: LineIndex {
+                block_lines: block_lines.max(1),
+                blocks: Vec::new(),
+                total_lines: 0,
+                total_bytes: 0,
+            },
+            at_line_start: true,
+        }
+    }
+
+    /// Feed the next chunk of the file
+    pub fn push(&mut self, chunk: &[u8]) {
+        let index = &mut self.index;
+        for (i, byte) in chunk.iter().enumerate() {
+            if self.at_line_start {
+                if index.total_lines.is_multiple_of(index.block_lines) {
+                    index.blocks.push(IndexBlock {
+                        line_number: index.total_lines + 1,
+                        byte_offset: index.total_bytes + i as u64,
+                        max_time: None,
+                    });
+                }
+                index.total_lines += 1;
+                self.at_line_start = false;
+            }
+            if *byte == b'\n' {
+                self.at_line_start = true;
+            }
+        }
+        index.total_bytes += chunk.len() as u64;
+    }
+
+    /// Number of bytes fed so far
+    pub fn size(&self) -> u64 {
+        self.index.total_bytes
+    }
+
+    /// Line offsets of the whole file, timestamps are added with [`LineIndex::record_time`]
+    /// once the entries are parsed
+    pub fn finish(self) -> LineIndex {
+        self.index
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,75 @@
+use super::super::*;
+use chrono::TimeZone;
+
+fn time(second: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 11, 23, 10, 0, second).unwrap()
+}
+
+fn build(text: &str, block_lines: usize, chunk_size: usize) -> LineIndex {
+    let mut builder = LineIndexBuilder::new(block_lines);
+    for chunk in text.as_bytes().chunks(chunk_size) {
+        builder.push(chunk);
+    }
+    builder.finish()
+}
+
+#[test]
+fn test_blocks_start_every_n_lines() {
+    let text = "a\nbb\nccc\r\ndddd\ne";
+
+    // chunk boundaries do not matter
+    for chunk_size in [1, 3, 100] {
+        let index = build(text, 2, chunk_size);
+        assert_eq!(index.total_lines, 5);
+        assert_eq!(index.total_bytes, text.len() as u64);
+        let starts: Vec<(usize, u64)> = index
+            .blocks
+            .iter()
+            .map(|block| (block.line_number, block.byte_offset))
+            .collect();
+        assert_eq!(starts, vec![(1, 0), (3, 5), (5, 15)]);
+    }
+
+    // a trailing newline does not start another line
+    assert_eq!(build("a\nb\n", 1, 10).total_lines, 2);
+}
+
+#[test]
+fn test_locate_offset() {
+    let index = build("1\n2\n3\n4\n5\n", 2, 10);
+
+    assert_eq!(index.locate_offset(0).unwrap().line_number, 1);
+    assert_eq!(index.locate_offset(3).unwrap().line_number, 1);
+    assert_eq!(index.locate_offset(4).unwrap().line_number, 3);
+    // offsets past the end are in the last block
+    assert_eq!(index.locate_offset(99).unwrap().byte_offset, 8);
+    assert!(build("", 2, 10).locate_offset(0).is_none());
+}
+
+#[test]
+fn test_locate_time_skips_only_earlier_blocks() {
+    let mut index = build("1\n2\n3\n4\n5\n6\n7\n8\n", 2, 10);
+    index.record_time(1, time(10));
+    // out of order entry, the block maximum stays at 30
+    index.record_time(3, time(30));
+    index.record_time(4, time(20));
+    index.record_time(6, time(25));
+    index.seal();
+
+    let max_times: Vec<_> = index.blocks.iter().map(|block| block.max_time).collect();
+    // the last block has no timestamps at all
+    assert_eq!(
+        max_times,
+        vec![
+            Some(time(10)),
+            Some(time(30)),
+            Some(time(30)),
+            Some(time(30))
+        ]
+    );
+
+    assert_eq!(index.locate_time(time(5)).unwrap().line_number, 1);
+    assert_eq!(index.locate_time(time(10)).unwrap().line_number, 1);
+    assert_eq!(index.locate_time(time(25)).unwrap().line_number, 3);
+    assert!(index.locate_time(time(31)).is_none());
+}
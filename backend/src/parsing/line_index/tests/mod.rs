@@ -0,0 +1 @@
+mod line_index_test;
@@ -0,0 +1,102 @@
+// chrono
+use chrono::{DateTime, Utc};
+// std
+use std::fmt;
+
+/// Interleaves the entries of several sources by timestamp, like a k-way merge of sorted files.
+/// Each source holds at most one entry, its next one. Entries without a parsed timestamp keep the
+/// time of the previous entry of their source, so they stay next to it.
+#[derive(Debug)]
+pub struct KWayMerge<T> {
+    /// Next entry of each source with the time it is sorted by
+    heads: Vec<Option<(Option<DateTime<Utc>>, T)>>,
+    /// Time of the last entry taken from each source
+    last_time: Vec<Option<DateTime<Utc>>>,
+}
+
+impl<T> KWayMerge<T> {
+    pub fn new(sources: usize) -> Self {
+        Self {
+            heads: (0..sources).map(|_| None).collect(),
+            last_time: vec![None; sources],
+        }
+    }
+
+    /// Whether the next entry of the source is needed before [`Self::pop`] can be called
+    pub fn needs(&self, source: usize) -> bool {
+        self.heads[source].is_none()
+    }
+
+    /// Set the next entry of a source
+    pub fn push(&mut self, source: usize, time: Option<DateTime<Utc>>, entry: T) {
+        let time = time.or(self.last_time[source]);
+        self.heads[source] = Some((time, entry));
+    }
+
+    /// Take the earliest entry with its source. Every source with entries left must have its next
+    /// entry pushed. Entries without any time come first, ties go to the earlier source.
+    pub fn pop(&mut self) -> Option<(usize, T)> {
+        let source = self
+            .heads
+            .iter()
+            .enumerate()
+            .filter_map(|(source, head)| head.as_ref().map(|(time, _)| (*time, source)))
+            .min()?
+            .1;
+        let (time, entry) = self.heads[source].take()?;
+        self.last_time[source] = time;
+        Some((source, entry))
+    }
+}
+
+/// Position in the merged entries of a session's files: the byte offset of the last entry sent
+/// from each file. Written as the offsets in file order separated by commas, `-` for files none
+/// of whose entries were sent yet. A single file's cursor is just its offset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeCursor(pub Vec<Option<u64>>);
+
+impl MergeCursor {
+    /// Cursor before the first entry of every file
+    pub fn start(sources: usize) -> Self {
+        Self(vec![None; sources])
+    }
+
+    /// Parse a cursor of a session with the given number of files
+    pub fn parse(text: &str, sources: usize) -> Result<Self, String> {
+        let invalid = || format!("Invalid cursor '{}'", text);
+        let offsets = text
+            .split(',')
+            .map(|offset| match offset.trim() {
+                "-" | "" => Ok(None),
+                offset => offset.parse().map(Some).map_err(|_| invalid()),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if offsets.len() != sources {
+            return Err(invalid());
+        }
+        Ok(Self(offsets))
+    }
+
+    /// Whether the entry of the source at the offset was sent before the cursor
+    pub fn covers(&self, source: usize, byte_offset: u64) -> bool {
+        self.0[source].is_some_and(|sent| byte_offset <= sent)
+    }
+}
+
+impl fmt::Display for MergeCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, offset) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            match offset {
+                Some(offset) => write!(f, "{}", offset)?,
+                None => f.write_str("-")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,85 @@
+use super::super::*;
+use chrono::TimeZone;
+
+fn time(second: u32) -> Option<DateTime<Utc>> {
+    Some(Utc.with_ymd_and_hms(2025, 11, 23, 10, 0, second).unwrap())
+}
+
+/// Merge sources given as (second, name) entries, `None` for entries without a timestamp
+fn merge(sources: Vec<Vec<(Option<u32>, &'static str)>>) -> Vec<&'static str> {
+    let mut sources: Vec<_> = sources.into_iter().map(|s| s.into_iter()).collect();
+    let mut merge = KWayMerge::new(sources.len());
+    let mut merged = Vec::new();
+    loop {
+        for (i, source) in sources.iter_mut().enumerate() {
+            if merge.needs(i) {
+                if let Some((second, name)) = source.next() {
+                    merge.push(i, second.and_then(time), name);
+                }
+            }
+        }
+        match merge.pop() {
+            Some((_, name)) => merged.push(name),
+            None => return merged,
+        }
+    }
+}
+
+#[test]
+fn test_entries_are_interleaved_by_time() {
+    let merged = merge(vec![
+        vec![(Some(1), "a1"), (Some(4), "a4"), (Some(5), "a5")],
+        vec![(Some(2), "b2"), (Some(3), "b3"), (Some(6), "b6")],
+        vec![],
+    ]);
+    assert_eq!(merged, vec!["a1", "b2", "b3", "a4", "a5", "b6"]);
+}
+
+#[test]
+fn test_ties_keep_source_order() {
+    let merged = merge(vec![
+        vec![(Some(1), "a1"), (Some(2), "a2")],
+        vec![(Some(1), "b1"), (Some(2), "b2")],
+    ]);
+    assert_eq!(merged, vec!["a1", "b1", "a2", "b2"]);
+}
+
+#[test]
+fn test_entries_without_time_stay_with_their_predecessor() {
+    let merged = merge(vec![
+        vec![(None, "a-header"), (Some(3), "a3"), (None, "a-trace")],
+        vec![(Some(1), "b1"), (Some(4), "b4")],
+    ]);
+    assert_eq!(merged, vec!["a-header", "b1", "a3", "a-trace", "b4"]);
+}
+
+#[test]
+fn test_cursor_round_trip() {
+    let cursor = MergeCursor::parse("154,-,0", 3).unwrap();
+    assert_eq!(cursor, MergeCursor(vec![Some(154), None, Some(0)]));
+    assert_eq!(cursor.to_string(), "154,-,0");
+
+    // a single file's cursor is its offset
+    assert_eq!(MergeCursor(vec![Some(42)]).to_string(), "42");
+    assert_eq!(
+        MergeCursor::parse("42", 1).unwrap(),
+        MergeCursor(vec![Some(42)])
+    );
+    assert_eq!(MergeCursor::start(2).to_string(), "-,-");
+}
+
+#[test]
+fn test_cursor_covers_sent_entries() {
+    let cursor = MergeCursor(vec![Some(100), None]);
+    assert!(cursor.covers(0, 0));
+    assert!(cursor.covers(0, 100));
+    assert!(!cursor.covers(0, 101));
+    assert!(!cursor.covers(1, 0));
+}
+
+#[test]
+fn test_invalid_cursors() {
+    assert!(MergeCursor::parse("1,2", 3).is_err());
+    assert!(MergeCursor::parse("abc", 1).is_err());
+    assert!(MergeCursor::parse("-1", 1).is_err());
+}
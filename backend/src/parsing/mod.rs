@@ -1 +1,16 @@
+pub mod context;
+pub mod detect;
+pub mod entry;
+pub mod exclusion;
+pub mod field_filter;
+pub mod filter;
+pub mod format;
+pub mod keyword;
+pub mod level;
+pub mod line_index;
+pub mod merge;
 pub mod parser;
+pub mod query;
+pub mod record;
+pub mod time_range;
+pub mod timestamp;
@@ -1,65 +1,195 @@
 // local
-use crate::model::log_summary::LogSummary;
-// once_cell
-use once_cell::sync::Lazy;
-// regex
-use regex::Regex;
+use crate::model::log_summary::{FileSummary, LogSummary};
+use crate::parsing::entry::LogEntry;
+use crate::parsing::filter::Filter;
+use crate::parsing::format::syslog::{FACILITY_FIELD, HOST_FIELD};
+use crate::parsing::level::level_order;
+use crate::parsing::record::LogRecord;
+// chrono
+use chrono::{DateTime, Utc};
+// indexmap
+use indexmap::IndexMap;
 // std
 use std::collections::{HashMap, HashSet};
 
-pub static LOG_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(
-        r"^\[(?P<ts>[^\]]+)\]\s\[(?P<level>[^\]]+)\]\s\[(?P<domain>[^\]]+)\]\s*(?P<message>.*)$",
-    )
-    .unwrap()
-});
-
-/// Parse log text and optionally filter by domains or levels
-pub fn parse_log(
-    log_text: &str,
-    filter_domains: Option<&Vec<String>>,
-    filter_levels: Option<&Vec<String>>,
-) -> LogSummary {
-    let mut total_lines = 0;
-    let mut levels = HashMap::new();
-    let mut domains = HashSet::new();
-    let mut start_timestamp: Option<String> = None;
-    let mut stop_timestamp: Option<String> = None;
-
-    for line in log_text.lines() {
-        if let Some(caps) = LOG_REGEX.captures(line) {
-            let ts = &caps["ts"];
-            let level = &caps["level"];
-            let domain = &caps["domain"];
-
-            if filter_levels
-                .as_ref()
-                .map_or(true, |l| l.contains(&level.to_string()))
-                && filter_domains
-                    .as_ref()
-                    .map_or(true, |d| d.contains(&domain.to_string()))
-            {
-                total_lines += 1;
-                *levels.entry(level.to_string()).or_insert(0) += 1;
-                domains.insert(domain.to_string());
-
-                // Track first and last timestamp
-                if start_timestamp.is_none() {
-                    start_timestamp = Some(ts.to_string());
+/// Summarize the parsed log entries matching the filter
+pub fn parse_log(entries: &[LogEntry], filter: &Filter) -> LogSummary {
+    let mut summary = SummaryBuilder::new(filter);
+    for entry in entries {
+        summary.add(&entry.record);
+    }
+    summary.finish()
+}
+
+/// Collects the summary of a log entry by entry, so large files don't have to be read at once
+pub struct SummaryBuilder<'a> {
+    filter: &'a Filter,
+    total_lines: usize,
+    levels: HashMap<String, usize>,
+    domains: HashSet<String>,
+    facilities: HashMap<String, usize>,
+    hosts: HashSet<String>,
+    first_timestamp: Option<String>,
+    last_timestamp: Option<String>,
+    // earliest and latest parsed timestamp, lines are not necessarily in order
+    earliest: Option<(DateTime<Utc>, String)>,
+    latest: Option<(DateTime<Utc>, String)>,
+}
+
+impl<'a> SummaryBuilder<'a> {
+    pub fn new(filter: &'a Filter) -> Self {
+        Self {
+            filter,
+            total_lines: 0,
+            levels: HashMap::new(),
+            domains: HashSet::new(),
+            facilities: HashMap::new(),
+            hosts: HashSet::new(),
+            first_timestamp: None,
+            last_timestamp: None,
+            earliest: None,
+            latest: None,
+        }
+    }
+
+    /// Add the record of an entry, multi-line entries are counted once based on their header line
+    pub fn add(&mut self, record: &LogRecord) {
+        if !self.filter.matches(record) {
+            return;
+        }
+
+        self.total_lines += 1;
+        // Formats like NDJSON may not have a level or domain on every record
+        if let Some(level) = &record.level {
+            *self.levels.entry(level.clone()).or_insert(0) += 1;
+        }
+        if let Some(domain) = &record.domain {
+            self.domains.insert(domain.clone());
+        }
+        if let Some(facility) = record.fields.get(FACILITY_FIELD) {
+            *self.facilities.entry(facility.to_string()).or_insert(0) += 1;
+        }
+        if let Some(host) = record.fields.get(HOST_FIELD) {
+            self.hosts.insert(host.to_string());
+        }
+
+        // Track first and last timestamp
+        if let Some(ts) = &record.timestamp {
+            if self.first_timestamp.is_none() {
+                self.first_timestamp = Some(ts.clone());
+            }
+            self.last_timestamp = Some(ts.clone());
+
+            if let Some(time) = record.time {
+                if self.earliest.as_ref().is_none_or(|(t, _)| time < *t) {
+                    self.earliest = Some((time, ts.clone()));
+                }
+                if self.latest.as_ref().is_none_or(|(t, _)| time >= *t) {
+                    self.latest = Some((time, ts.clone()));
                 }
-                stop_timestamp = Some(ts.to_string());
             }
         }
     }
 
-    LogSummary {
-        total_lines,
-        levels,
-        unique_domains: domains.into_iter().collect(),
-        start_timestamp,
-        stop_timestamp,
+    pub fn finish(self) -> LogSummary {
+        // Fall back to the first and last raw timestamps if none of them could be parsed
+        let (start_time, start_timestamp) = match self.earliest {
+            Some((time, ts)) => (Some(time), Some(ts)),
+            None => (None, self.first_timestamp),
+        };
+        let (stop_time, stop_timestamp) = match self.latest {
+            Some((time, ts)) => (Some(time), Some(ts)),
+            None => (None, self.last_timestamp),
+        };
+        let duration_seconds = start_time
+            .zip(stop_time)
+            .and_then(|(start, stop)| (stop - start).num_nanoseconds())
+            .map(|nanos| nanos as f64 / 1e9);
+
+        // Report levels in severity order
+        let mut levels: Vec<(String, usize)> = self.levels.into_iter().collect();
+        levels.sort_by(|(a, _), (b, _)| level_order(a).cmp(&level_order(b)));
+
+        LogSummary {
+            total_lines: self.total_lines,
+            levels: levels.into_iter().collect::<IndexMap<_, _>>(),
+            unique_domains: self.domains.into_iter().collect(),
+            facilities: self.facilities,
+            unique_hosts: self.hosts.into_iter().collect(),
+            start_timestamp,
+            stop_timestamp,
+            start_time,
+            stop_time,
+            duration_seconds,
+            files: Vec::new(),
+        }
     }
 }
 
+/// Combine the summaries of the files of a session, given with their source name and format.
+/// The combined summary lists the statistics of each file.
+pub fn merge_summaries<'a>(
+    files: impl IntoIterator<Item = (&'a str, &'a str, &'a LogSummary)>,
+) -> LogSummary {
+    let mut merged = LogSummary::default();
+    let mut levels: HashMap<String, usize> = HashMap::new();
+    let mut domains: HashSet<String> = HashSet::new();
+    let mut hosts: HashSet<String> = HashSet::new();
+
+    for (source, format, summary) in files {
+        merged.total_lines += summary.total_lines;
+        for (level, count) in &summary.levels {
+            *levels.entry(level.clone()).or_insert(0) += count;
+        }
+        domains.extend(summary.unique_domains.iter().cloned());
+        for (facility, count) in &summary.facilities {
+            *merged.facilities.entry(facility.clone()).or_insert(0) += count;
+        }
+        hosts.extend(summary.unique_hosts.iter().cloned());
+
+        // Parsed times win over raw timestamps, otherwise the first file's start and the last
+        // file's stop are kept
+        let earlier = match (summary.start_time, merged.start_time) {
+            (Some(time), Some(start)) => time < start,
+            (Some(_), None) => true,
+            (None, _) => merged.start_timestamp.is_none(),
+        };
+        if earlier && summary.start_timestamp.is_some() {
+            merged.start_time = summary.start_time;
+            merged.start_timestamp = summary.start_timestamp.clone();
+        }
+        let later = match (summary.stop_time, merged.stop_time) {
+            (Some(time), Some(stop)) => time >= stop,
+            (Some(_), None) => true,
+            (None, stop) => stop.is_none(),
+        };
+        if later && summary.stop_timestamp.is_some() {
+            merged.stop_time = summary.stop_time;
+            merged.stop_timestamp = summary.stop_timestamp.clone();
+        }
+
+        merged.files.push(FileSummary {
+            source: source.to_string(),
+            format: format.to_string(),
+            total_lines: summary.total_lines,
+            levels: summary.levels.clone(),
+            start_time: summary.start_time,
+            stop_time: summary.stop_time,
+        });
+    }
+
+    merged.duration_seconds = merged
+        .start_time
+        .zip(merged.stop_time)
+        .and_then(|(start, stop)| (stop - start).num_nanoseconds())
+        .map(|nanos| nanos as f64 / 1e9);
+    let mut levels: Vec<(String, usize)> = levels.into_iter().collect();
+    levels.sort_by(|(a, _), (b, _)| level_order(a).cmp(&level_order(b)));
+    merged.levels = levels.into_iter().collect();
+    merged.unique_domains = domains.into_iter().collect();
+    merged.unique_hosts = hosts.into_iter().collect();
+    merged
+}
+
 #[cfg(test)]
 mod tests;
@@ -1,8 +1,12 @@
 use super::super::*;
-use std::collections::{HashMap, HashSet};
+use crate::parsing::entry::parse_entries;
+use crate::parsing::filter::{FilterError, FilterSpec};
+use crate::parsing::format::bracketed::BracketedFormat;
+use crate::parsing::level::Severity;
+use std::collections::HashSet;
 
 fn sample_log() -> String {
-    let lines = vec![
+    let lines = [
         "[2025-11-23 10:00:00] [INFO] [core] Starting process",
         "[2025-11-23 10:00:01] [DEBUG] [network] Connection established",
         "[2025-11-23 10:00:02] [WARN] [core] Unexpected value encountered",
@@ -11,21 +15,32 @@ fn sample_log() -> String {
     lines.join("\n")
 }
 
+fn compile(spec: FilterSpec) -> Filter {
+    Filter::compile(&spec, None, None).unwrap()
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|s| s.to_string()).collect()
+}
+
 #[test]
 fn test_parse_no_filters_returns_all_lines() {
     let log = sample_log();
 
-    let summary = parse_log(&log, None, None);
+    let summary = parse_log(&parse_entries(&BracketedFormat, &log), &Filter::default());
 
     assert_eq!(summary.total_lines, 4);
 
-    let mut expected_levels = HashMap::new();
-    expected_levels.insert("INFO".to_string(), 1usize);
-    expected_levels.insert("DEBUG".to_string(), 1usize);
-    expected_levels.insert("WARN".to_string(), 1usize);
-    expected_levels.insert("ERROR".to_string(), 1usize);
-
-    assert_eq!(summary.levels, expected_levels);
+    // levels are reported in severity order
+    let levels: Vec<(&str, usize)> = summary
+        .levels
+        .iter()
+        .map(|(level, count)| (level.as_str(), *count))
+        .collect();
+    assert_eq!(
+        levels,
+        vec![("DEBUG", 1), ("INFO", 1), ("WARN", 1), ("ERROR", 1)]
+    );
 
     let domains_set: HashSet<_> = summary.unique_domains.into_iter().collect();
     let expected_domains: HashSet<String> = ["core", "network", "data_acq"]
@@ -40,8 +55,14 @@ fn test_parse_all_filtered_returns_zero() {
     let log = sample_log();
 
     // filter by a level that does not exist in the sample
-    let level_filter = vec!["TRACE".to_string()];
-    let summary = parse_log(&log, None, Some(&level_filter));
+    let level_filter = strings(&["TRACE"]);
+    let summary = parse_log(
+        &parse_entries(&BracketedFormat, &log),
+        &compile(FilterSpec {
+            levels: Some(level_filter),
+            ..Default::default()
+        }),
+    );
 
     assert_eq!(summary.total_lines, 0);
     assert!(summary.levels.is_empty());
@@ -53,18 +74,247 @@ fn test_parse_some_filtered_returns_subset() {
     let log = sample_log();
 
     // keep only INFO and WARN
-    let keep_levels = vec!["INFO".to_string(), "WARN".to_string()];
-    let summary = parse_log(&log, None, Some(&keep_levels));
+    let keep_levels = strings(&["INFO", "WARN"]);
+    let summary = parse_log(
+        &parse_entries(&BracketedFormat, &log),
+        &compile(FilterSpec {
+            levels: Some(keep_levels),
+            ..Default::default()
+        }),
+    );
 
     assert_eq!(summary.total_lines, 2);
 
-    let mut expected_levels = HashMap::new();
-    expected_levels.insert("INFO".to_string(), 1usize);
-    expected_levels.insert("WARN".to_string(), 1usize);
-    assert_eq!(summary.levels, expected_levels);
+    assert_eq!(summary.levels.get("INFO"), Some(&1));
+    assert_eq!(summary.levels.get("WARN"), Some(&1));
+    assert_eq!(summary.levels.len(), 2);
 
     let domains_set: HashSet<_> = summary.unique_domains.into_iter().collect();
     // both INFO and WARN in sample are from 'core'
     let expected_domains: HashSet<String> = ["core"].iter().map(|s| s.to_string()).collect();
     assert_eq!(domains_set, expected_domains);
 }
+
+#[test]
+fn test_parse_syslog_reports_facilities_and_hosts() {
+    use crate::parsing::format::syslog::Rfc3164Format;
+
+    let log = [
+        "<34>Oct 11 22:14:15 alpha su[230]: 'su root' failed",
+        "<86>Oct 11 22:14:16 beta sshd[12]: Accepted publickey",
+        "<38>Oct 11 22:14:17 alpha sshd[13]: Connection closed",
+    ]
+    .join("\n");
+
+    let summary = parse_log(&parse_entries(&Rfc3164Format, &log), &Filter::default());
+
+    assert_eq!(summary.total_lines, 3);
+    assert_eq!(summary.facilities.get("auth"), Some(&2));
+    assert_eq!(summary.facilities.get("authpriv"), Some(&1));
+    let hosts: HashSet<_> = summary.unique_hosts.into_iter().collect();
+    assert_eq!(
+        hosts,
+        HashSet::from(["alpha".to_string(), "beta".to_string()])
+    );
+}
+
+#[test]
+fn test_parse_reports_true_time_range() {
+    // lines are not in chronological order
+    let log = [
+        "[2025-11-23 10:00:05] [INFO] [core] Second",
+        "[2025-11-23 10:00:00.5] [INFO] [core] First",
+        "[2025-11-23 10:01:00] [INFO] [core] Last",
+        "[2025-11-23 10:00:30] [INFO] [core] Third",
+    ]
+    .join("\n");
+
+    let summary = parse_log(&parse_entries(&BracketedFormat, &log), &Filter::default());
+
+    assert_eq!(
+        summary.start_timestamp.as_deref(),
+        Some("2025-11-23 10:00:00.5")
+    );
+    assert_eq!(
+        summary.stop_timestamp.as_deref(),
+        Some("2025-11-23 10:01:00")
+    );
+    assert_eq!(
+        summary.start_time.unwrap().to_rfc3339(),
+        "2025-11-23T10:00:00.500+00:00"
+    );
+    assert_eq!(summary.duration_seconds, Some(59.5));
+}
+
+#[test]
+fn test_parse_counts_only_entries_in_time_range() {
+    let log = sample_log();
+
+    let error = Filter::compile(
+        &FilterSpec {
+            from: Some("2025-11-23 10:00:01".to_string()),
+            to: Some("-1s".to_string()),
+            ..Default::default()
+        },
+        None,
+        None,
+    )
+    .unwrap_err();
+    assert!(
+        matches!(error, FilterError::Invalid(_)),
+        "relative bounds need the log span"
+    );
+
+    let summary = parse_log(
+        &parse_entries(&BracketedFormat, &log),
+        &compile(FilterSpec {
+            from: Some("2025-11-23 10:00:01".to_string()),
+            to: Some("2025-11-23 10:00:02".to_string()),
+            ..Default::default()
+        }),
+    );
+
+    assert_eq!(summary.total_lines, 2);
+    assert_eq!(
+        summary.start_timestamp.as_deref(),
+        Some("2025-11-23 10:00:01")
+    );
+    assert_eq!(
+        summary.stop_timestamp.as_deref(),
+        Some("2025-11-23 10:00:02")
+    );
+}
+
+#[test]
+fn test_parse_counts_only_entries_matching_query() {
+    let log = sample_log();
+    let summary = parse_log(
+        &parse_entries(&BracketedFormat, &log),
+        &compile(FilterSpec {
+            query: Some("domain:core AND NOT level:INFO".to_string()),
+            ..Default::default()
+        }),
+    );
+
+    assert_eq!(summary.total_lines, 1);
+    assert_eq!(summary.levels.get("WARN"), Some(&1));
+}
+
+#[test]
+fn test_parse_normalizes_levels_and_filters_by_min_level() {
+    let log = [
+        "[2025-11-23 10:00:00] [information] [core] Starting process",
+        "[2025-11-23 10:00:01] [warning] [network] Slow response",
+        "[2025-11-23 10:00:02] [W] [network] Slow response",
+        "[2025-11-23 10:00:03] [CRIT] [core] Out of memory",
+        "[2025-11-23 10:00:04] [custom] [core] Unknown level",
+    ]
+    .join("\n");
+
+    let summary = parse_log(&parse_entries(&BracketedFormat, &log), &Filter::default());
+    let levels: Vec<(&str, usize)> = summary
+        .levels
+        .iter()
+        .map(|(level, count)| (level.as_str(), *count))
+        .collect();
+    assert_eq!(
+        levels,
+        vec![("INFO", 1), ("WARN", 2), ("FATAL", 1), ("custom", 1)]
+    );
+
+    // aliases in the level filter match the normalized levels
+    let level_filter = strings(&["err", "Warning"]);
+    let summary = parse_log(
+        &parse_entries(&BracketedFormat, &log),
+        &compile(FilterSpec {
+            levels: Some(level_filter),
+            ..Default::default()
+        }),
+    );
+    assert_eq!(summary.total_lines, 2);
+
+    // unknown levels are below every threshold
+    let summary = parse_log(
+        &parse_entries(&BracketedFormat, &log),
+        &compile(FilterSpec {
+            min_level: Some(Severity::Warn),
+            ..Default::default()
+        }),
+    );
+    assert_eq!(summary.total_lines, 3);
+    assert!(!summary.levels.contains_key("INFO"));
+    assert!(!summary.levels.contains_key("custom"));
+}
+
+#[test]
+fn test_parse_exclusions_take_priority() {
+    let log = sample_log();
+    let summary = parse_log(
+        &parse_entries(&BracketedFormat, &log),
+        &compile(FilterSpec {
+            levels: Some(strings(&["INFO", "WARN"])),
+            exclude_keywords: strings(&["Unexpected"]),
+            ..Default::default()
+        }),
+    );
+
+    assert_eq!(summary.total_lines, 1);
+    assert_eq!(summary.levels.get("INFO"), Some(&1));
+    assert!(!summary.levels.contains_key("WARN"));
+}
+
+#[test]
+fn test_merge_summaries_combines_files() {
+    let api = parse_log(
+        &parse_entries(
+            &BracketedFormat,
+            &[
+                "[2025-11-23 10:00:05] [ERROR] [api] Request failed",
+                "[2025-11-23 10:00:09] [INFO] [api] Request served",
+            ]
+            .join("\n"),
+        ),
+        &Filter::default(),
+    );
+    let db = parse_log(
+        &parse_entries(
+            &BracketedFormat,
+            &[
+                "[2025-11-23 10:00:01] [INFO] [db] Connected",
+                "[2025-11-23 10:00:04] [WARN] [db] Slow query",
+                "[2025-11-23 10:00:06] [INFO] [db] Connected",
+            ]
+            .join("\n"),
+        ),
+        &Filter::default(),
+    );
+
+    let summary = merge_summaries([("api.log", "bracketed", &api), ("db.log", "bracketed", &db)]);
+
+    assert_eq!(summary.total_lines, 5);
+    let levels: Vec<(&str, usize)> = summary
+        .levels
+        .iter()
+        .map(|(level, count)| (level.as_str(), *count))
+        .collect();
+    assert_eq!(levels, vec![("INFO", 3), ("WARN", 1), ("ERROR", 1)]);
+    let domains: HashSet<&str> = summary.unique_domains.iter().map(String::as_str).collect();
+    assert_eq!(domains, HashSet::from(["api", "db"]));
+    assert_eq!(
+        summary.start_timestamp.as_deref(),
+        Some("2025-11-23 10:00:01")
+    );
+    assert_eq!(
+        summary.stop_timestamp.as_deref(),
+        Some("2025-11-23 10:00:09")
+    );
+    assert_eq!(summary.duration_seconds, Some(8.0));
+
+    let files: Vec<(&str, usize)> = summary
+        .files
+        .iter()
+        .map(|file| (file.source.as_str(), file.total_lines))
+        .collect();
+    assert_eq!(files, vec![("api.log", 2), ("db.log", 3)]);
+    assert_eq!(summary.files[1].start_time, db.start_time);
+}
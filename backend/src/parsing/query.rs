@@ -0,0 +1,384 @@
+// local
+use crate::parsing::field_filter::{FieldFilter, FieldOp};
+use crate::parsing::keyword::{KeywordMatcher, MatchMode};
+use crate::parsing::level::normalize_level;
+use crate::parsing::record::{FieldValue, LogRecord};
+// std
+use std::fmt;
+
+/// A parsed filter query like `level:ERROR AND (domain:network OR domain:core) AND NOT "retrying"`.
+///
+/// - terms without a field prefix match keywords in the message according to the match mode,
+///   quotes allow spaces
+/// - `level:`, `domain:` and `message:` (or `msg:`) address the parts of a record,
+///   any other prefix addresses an additional field, e.g. `status:>=500`
+/// - `AND`, `OR` and `NOT` combine terms, terms next to each other are AND-ed
+/// - `NOT` binds strongest, then `AND`, then `OR`, parentheses group
+#[derive(Debug, Clone)]
+pub enum Query {
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+    Not(Box<Query>),
+    Term(Term),
+}
+
+/// A single condition of a [`Query`]
+#[derive(Debug, Clone)]
+pub enum Term {
+    /// Message matches the keyword
+    Keyword(KeywordMatcher),
+    /// Level equals the text
+    Level(String),
+    /// Domain equals the text
+    Domain(String),
+    /// Condition on an additional field
+    Field(FieldFilter),
+}
+
+/// Error while parsing a query
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryError {
+    pub message: String,
+    /// Character position in the query the error refers to (0-based)
+    pub position: usize,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl Query {
+    /// Parse a query, keywords are matched according to `mode`
+    pub fn parse(text: &str, mode: MatchMode) -> Result<Self, QueryError> {
+        let tokens = tokenize(text, mode)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            end: text.chars().count(),
+            depth: 0,
+        };
+
+        let query = parser.parse_or()?;
+        match parser.peek() {
+            None => Ok(query),
+            Some((Token::RParen, position)) => Err(QueryError {
+                message: "Unmatched closing parenthesis".to_string(),
+                position: *position,
+            }),
+            Some((_, position)) => Err(QueryError {
+                message: "Unexpected token".to_string(),
+                position: *position,
+            }),
+        }
+    }
+
+    /// Keywords a matching message can contain, i.e. all keywords not negated by `NOT`
+    pub fn positive_keywords(&self) -> Vec<&KeywordMatcher> {
+        match self {
+            Query::And(a, b) | Query::Or(a, b) => {
+                let mut keywords = a.positive_keywords();
+                keywords.extend(b.positive_keywords());
+                keywords
+            }
+            Query::Not(_) => Vec::new(),
+            Query::Term(Term::Keyword(keyword)) => vec![keyword],
+            Query::Term(_) => Vec::new(),
+        }
+    }
+
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        match self {
+            Query::And(a, b) => a.matches(record) && b.matches(record),
+            Query::Or(a, b) => a.matches(record) || b.matches(record),
+            Query::Not(q) => !q.matches(record),
+            Query::Term(term) => term.matches(record),
+        }
+    }
+}
+
+impl Term {
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        match self {
+            Term::Keyword(keyword) => keyword.is_match(&record.message),
+            Term::Level(level) => record.level_str() == level,
+            Term::Domain(domain) => record.domain_str() == domain,
+            Term::Field(filter) => filter.matches(record),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Term(Term),
+}
+
+fn tokenize(text: &str, mode: MatchMode) -> Result<Vec<(Token, usize)>, QueryError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        match c {
+            '(' => {
+                tokens.push((Token::LParen, start));
+                i += 1;
+            }
+            ')' => {
+                tokens.push((Token::RParen, start));
+                i += 1;
+            }
+            '"' => {
+                let (value, next) = read_quoted(&chars, i)?;
+                tokens.push((Token::Term(keyword_term(&value, mode, start)?), start));
+                i = next;
+            }
+            _ => {
+                while i < chars.len() && !is_delimiter(chars[i]) {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                let token = match word.as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => match word.split_once(':') {
+                        Some((field, value)) => {
+                            // field:"quoted value"
+                            let value = if value.is_empty() && chars.get(i) == Some(&'"') {
+                                let (value, next) = read_quoted(&chars, i)?;
+                                i = next;
+                                value
+                            } else {
+                                value.to_string()
+                            };
+                            Token::Term(field_term(field, value, mode, start)?)
+                        }
+                        None => Token::Term(keyword_term(&word, mode, start)?),
+                    },
+                };
+                tokens.push((token, start));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || c == '(' || c == ')' || c == '"'
+}
+
+/// Read a quoted string starting at the opening quote, `\"` and `\\` are unescaped
+fn read_quoted(chars: &[char], start: usize) -> Result<(String, usize), QueryError> {
+    let mut value = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '"' => return Ok((value, i + 1)),
+            '\\' if i + 1 < chars.len() => {
+                value.push(chars[i + 1]);
+                i += 2;
+            }
+            c => {
+                value.push(c);
+                i += 1;
+            }
+        }
+    }
+    Err(QueryError {
+        message: "Unterminated quote".to_string(),
+        position: start,
+    })
+}
+
+fn keyword_term(keyword: &str, mode: MatchMode, position: usize) -> Result<Term, QueryError> {
+    KeywordMatcher::new(keyword, mode)
+        .map(Term::Keyword)
+        .map_err(|message| QueryError { message, position })
+}
+
+fn field_term(
+    field: &str,
+    value: String,
+    mode: MatchMode,
+    position: usize,
+) -> Result<Term, QueryError> {
+    if field.is_empty() {
+        return Err(QueryError {
+            message: "Missing field name".to_string(),
+            position,
+        });
+    }
+    if value.is_empty() {
+        return Err(QueryError {
+            message: format!("Missing value for field '{}'", field),
+            position,
+        });
+    }
+
+    Ok(match field {
+        "level" => Term::Level(normalize_level(&value)),
+        "domain" => Term::Domain(value),
+        "message" | "msg" => keyword_term(&value, mode, position)?,
+        _ => {
+            // optional comparison operator, e.g. status:>=500
+            let (op, value) = [
+                (">=", FieldOp::Ge),
+                ("<=", FieldOp::Le),
+                ("!=", FieldOp::Ne),
+                (">", FieldOp::Gt),
+                ("<", FieldOp::Lt),
+                ("=", FieldOp::Eq),
+            ]
+            .into_iter()
+            .find_map(|(prefix, op)| value.strip_prefix(prefix).map(|v| (op, v)))
+            .unwrap_or((FieldOp::Eq, value.as_str()));
+
+            Term::Field(FieldFilter {
+                key: field.to_string(),
+                op,
+                value: FieldValue::infer(value),
+            })
+        }
+    })
+}
+
+/// Maximum nesting of parentheses and `NOT`, deeper queries are rejected instead of overflowing
+/// the stack while parsing or matching
+pub const MAX_QUERY_DEPTH: usize = 64;
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    /// Length of the query, the position reported for errors at the end
+    end: usize,
+    /// Current nesting of parentheses and `NOT`
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&(Token, usize)> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next_if(&mut self, token: &Token) -> bool {
+        let same_kind = |t: &Token| std::mem::discriminant(t) == std::mem::discriminant(token);
+        if self.peek().is_some_and(|(t, _)| same_kind(t)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Query, QueryError> {
+        let mut operands = vec![self.parse_and()?];
+        while self.next_if(&Token::Or) {
+            operands.push(self.parse_and()?);
+        }
+        Ok(balanced(operands, Query::Or))
+    }
+
+    fn parse_and(&mut self) -> Result<Query, QueryError> {
+        let mut operands = vec![self.parse_not()?];
+        loop {
+            if self.next_if(&Token::And) {
+                operands.push(self.parse_not()?);
+            } else if matches!(
+                self.peek(),
+                Some((Token::Term(_) | Token::Not | Token::LParen, _))
+            ) {
+                // implicit AND
+                operands.push(self.parse_not()?);
+            } else {
+                return Ok(balanced(operands, Query::And));
+            }
+        }
+    }
+
+    fn parse_not(&mut self) -> Result<Query, QueryError> {
+        let position = self.peek().map(|(_, position)| *position);
+        if self.next_if(&Token::Not) {
+            self.enter(position.unwrap_or(self.end))?;
+            let query = Query::Not(Box::new(self.parse_not()?));
+            self.depth -= 1;
+            return Ok(query);
+        }
+        self.parse_primary()
+    }
+
+    /// Go one level deeper, fails past [`MAX_QUERY_DEPTH`]
+    fn enter(&mut self, position: usize) -> Result<(), QueryError> {
+        if self.depth >= MAX_QUERY_DEPTH {
+            return Err(QueryError {
+                message: "Query nested too deeply".to_string(),
+                position,
+            });
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn parse_primary(&mut self) -> Result<Query, QueryError> {
+        let Some((token, position)) = self.tokens.get(self.pos).cloned() else {
+            return Err(QueryError {
+                message: "Unexpected end of query".to_string(),
+                position: self.end,
+            });
+        };
+        self.pos += 1;
+
+        match token {
+            Token::Term(term) => Ok(Query::Term(term)),
+            Token::LParen => {
+                self.enter(position)?;
+                let query = self.parse_or()?;
+                self.depth -= 1;
+                if !self.next_if(&Token::RParen) {
+                    return Err(QueryError {
+                        message: "Missing closing parenthesis".to_string(),
+                        position,
+                    });
+                }
+                Ok(query)
+            }
+            Token::RParen => Err(QueryError {
+                message: "Unexpected closing parenthesis".to_string(),
+                position,
+            }),
+            Token::And | Token::Or | Token::Not => Err(QueryError {
+                message: "Expected a term".to_string(),
+                position,
+            }),
+        }
+    }
+}
+
+/// Combine operands of the same operator into a balanced tree, so long chains like
+/// `a AND b AND c ...` stay shallow. The operands keep their order.
+fn balanced(mut operands: Vec<Query>, op: fn(Box<Query>, Box<Query>) -> Query) -> Query {
+    if operands.len() == 1 {
+        return operands.pop().unwrap();
+    }
+    let right = operands.split_off(operands.len() / 2);
+    op(
+        Box::new(balanced(operands, op)),
+        Box::new(balanced(right, op)),
+    )
+}
+
+#[cfg(test)]
+mod tests;
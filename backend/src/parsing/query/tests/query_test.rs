@@ -0,0 +1,120 @@
+use super::super::*;
+
+fn record(level: &str, domain: &str, message: &str) -> LogRecord {
+    LogRecord {
+        level: Some(level.to_string()),
+        domain: Some(domain.to_string()),
+        message: message.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_query_with_grouping_and_negation() {
+    let query = Query::parse(
+        r#"level:ERROR AND (domain:network OR domain:core) AND NOT "retrying now""#,
+        MatchMode::Literal,
+    )
+    .unwrap();
+
+    assert!(query.matches(&record("ERROR", "network", "connection lost")));
+    assert!(query.matches(&record("ERROR", "core", "failed")));
+    assert!(!query.matches(&record("ERROR", "core", "failed, retrying now")));
+    assert!(!query.matches(&record("ERROR", "data_acq", "failed")));
+    assert!(!query.matches(&record("WARN", "core", "failed")));
+}
+
+#[test]
+fn test_query_precedence_and_implicit_and() {
+    // AND binds stronger than OR
+    let query = Query::parse("level:WARN OR level:ERROR timeout", MatchMode::Literal).unwrap();
+
+    assert!(query.matches(&record("WARN", "core", "anything")));
+    assert!(query.matches(&record("ERROR", "core", "read timeout")));
+    assert!(!query.matches(&record("ERROR", "core", "read failed")));
+}
+
+#[test]
+fn test_query_field_terms() {
+    let query = Query::parse(r#"status:>=500 AND peer:"10.0.0.5""#, MatchMode::Literal).unwrap();
+    let mut matching = record("ERROR", "http", "bad gateway");
+    matching
+        .fields
+        .insert("status".to_string(), FieldValue::Number(502.0));
+    matching.fields.insert(
+        "peer".to_string(),
+        FieldValue::String("10.0.0.5".to_string()),
+    );
+
+    assert!(query.matches(&matching));
+    assert!(!query.matches(&record("ERROR", "http", "bad gateway")));
+}
+
+#[test]
+fn test_query_errors_point_at_position() {
+    let error = |text: &str| Query::parse(text, MatchMode::Literal).unwrap_err();
+
+    assert_eq!(error("level:ERROR AND").position, 15);
+    assert_eq!(error("(domain:core OR level:WARN").position, 0);
+    assert_eq!(error("domain:core)").position, 11);
+    assert_eq!(error(r#"level:ERROR "open"#).position, 12);
+    assert_eq!(error("level: x").position, 0);
+    assert_eq!(error("NOT OR x").position, 4);
+}
+
+#[test]
+fn test_query_keywords_use_match_mode() {
+    let query = Query::parse(r#"level:ERROR "sensor [a-c]""#, MatchMode::Regex).unwrap();
+    assert!(query.matches(&record("ERROR", "core", "failed to read sensor b")));
+    assert!(!query.matches(&record("ERROR", "core", "failed to read sensor d")));
+
+    let query = Query::parse("msg:TIMEOUT", MatchMode::IgnoreCase).unwrap();
+    assert!(query.matches(&record("ERROR", "core", "I/O timeout")));
+
+    let error = Query::parse(r#"level:ERROR "sensor [""#, MatchMode::Regex).unwrap_err();
+    assert_eq!(error.position, 12);
+    assert!(error.message.starts_with("Invalid regex"));
+}
+
+#[test]
+fn test_positive_keywords_skip_negated_terms() {
+    let query = Query::parse(
+        r#"(timeout OR message:refused) AND NOT retrying AND level:ERROR"#,
+        MatchMode::Literal,
+    )
+    .unwrap();
+
+    let keywords: Vec<String> = query
+        .positive_keywords()
+        .into_iter()
+        .map(|keyword| format!("{:?}", keyword))
+        .collect();
+    assert_eq!(keywords.len(), 2);
+    assert!(keywords[0].contains("timeout"));
+    assert!(keywords[1].contains("refused"));
+}
+
+#[test]
+fn test_deeply_nested_queries_are_rejected() {
+    let nested = |depth: usize| format!("{}x{}", "(".repeat(depth), ")".repeat(depth));
+    assert!(Query::parse(&nested(MAX_QUERY_DEPTH), MatchMode::Literal).is_ok());
+
+    let error = Query::parse(&nested(100_000), MatchMode::Literal).unwrap_err();
+    assert_eq!(error.message, "Query nested too deeply");
+    assert_eq!(error.position, MAX_QUERY_DEPTH);
+
+    let error = Query::parse(&"NOT ".repeat(100_000), MatchMode::Literal).unwrap_err();
+    assert_eq!(error.message, "Query nested too deeply");
+}
+
+#[test]
+fn test_long_chains_are_parsed() {
+    let chain = vec!["x"; 100_000].join(" AND ");
+    let query = Query::parse(&chain, MatchMode::Literal).unwrap();
+    assert!(query.matches(&record("INFO", "core", "x")));
+    assert!(!query.matches(&record("INFO", "core", "y")));
+
+    let chain = vec!["level:ERROR"; 100_000].join(" OR ") + " OR x";
+    let query = Query::parse(&chain, MatchMode::Literal).unwrap();
+    assert!(query.matches(&record("INFO", "core", "x")));
+}
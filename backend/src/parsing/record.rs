@@ -0,0 +1,66 @@
+// chrono
+use chrono::{DateTime, Utc};
+// std
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Value of an additional, format specific field
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl FieldValue {
+    /// Infer the type of a raw text value, e.g. "true" becomes a bool and "12.5" a number
+    pub fn infer(raw: &str) -> Self {
+        match raw {
+            "true" => FieldValue::Bool(true),
+            "false" => FieldValue::Bool(false),
+            _ => match raw.parse::<f64>() {
+                Ok(n) if n.is_finite() => FieldValue::Number(n),
+                _ => FieldValue::String(raw.to_string()),
+            },
+        }
+    }
+}
+
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValue::Bool(b) => write!(f, "{}", b),
+            FieldValue::Number(n) => write!(f, "{}", n),
+            FieldValue::String(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// A single log line broken up into its structured parts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogRecord {
+    /// Timestamp as it appears in the line
+    pub timestamp: Option<String>,
+    /// Parsed timestamp, `None` if the line has none or it is in an unknown format
+    pub time: Option<DateTime<Utc>>,
+    /// Log level, e.g. "INFO"
+    pub level: Option<String>,
+    /// Domain (component, logger, target, ...) the line was logged from
+    pub domain: Option<String>,
+    /// The actual log message
+    pub message: String,
+    /// Additional format specific fields
+    pub fields: BTreeMap<String, FieldValue>,
+}
+
+impl LogRecord {
+    /// Level of the record, or an empty string if there is none
+    pub fn level_str(&self) -> &str {
+        self.level.as_deref().unwrap_or("")
+    }
+
+    /// Domain of the record, or an empty string if there is none
+    pub fn domain_str(&self) -> &str {
+        self.domain.as_deref().unwrap_or("")
+    }
+}
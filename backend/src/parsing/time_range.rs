@@ -0,0 +1,156 @@
+// local
+use crate::parsing::record::LogRecord;
+use crate::parsing::timestamp::parse_timestamp;
+// chrono
+use chrono::{DateTime, Duration, Utc};
+
+/// One end of a time range as given by a client
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeBound {
+    /// An absolute timestamp, e.g. `2025-11-17T12:50:00Z`
+    Absolute(DateTime<Utc>),
+    /// Relative to the start of the log, e.g. `+30s`
+    FromStart(Duration),
+    /// Relative to the end of the log, e.g. `-15m`
+    FromEnd(Duration),
+}
+
+impl TimeBound {
+    /// Parse an absolute timestamp in any format supported by [`parse_timestamp`] or a relative
+    /// expression like `-15m`, `+30s` or `-1h30m`. Units are ms, s, m, h and d.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        if let Some(relative) = text.strip_prefix('+') {
+            return parse_duration(relative).map(TimeBound::FromStart);
+        }
+        if let Some(relative) = text.strip_prefix('-') {
+            return parse_duration(relative).map(TimeBound::FromEnd);
+        }
+        parse_timestamp(text)
+            .map(TimeBound::Absolute)
+            .ok_or_else(|| format!("Invalid time '{}'", text))
+    }
+
+    /// Turn the bound into an absolute timestamp given the time span of the log. `None` if the
+    /// bound is relative and the log has no parsed timestamps.
+    pub fn resolve(
+        &self,
+        log_start: Option<DateTime<Utc>>,
+        log_end: Option<DateTime<Utc>>,
+    ) -> Result<Option<DateTime<Utc>>, String> {
+        let resolved = match self {
+            TimeBound::Absolute(ts) => return Ok(Some(*ts)),
+            TimeBound::FromStart(offset) => {
+                log_start.map(|start| start.checked_add_signed(*offset))
+            }
+            TimeBound::FromEnd(offset) => log_end.map(|end| end.checked_sub_signed(*offset)),
+        };
+        resolved
+            .map(|time| time.ok_or_else(|| "Relative time out of range".to_string()))
+            .transpose()
+    }
+}
+
+/// Parse a duration like `15m` or `1h30m`
+fn parse_duration(text: &str) -> Result<Duration, String> {
+    let invalid = || {
+        format!(
+            "Invalid relative time '{}', expected e.g. -15m or +30s",
+            text
+        )
+    };
+    let mut total = Duration::zero();
+    let mut rest = text;
+
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .ok_or_else(invalid)?;
+        let value: f64 = rest[..digits].parse().map_err(|_| invalid())?;
+        rest = &rest[digits..];
+
+        let unit_len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let millis_per_unit = match &rest[..unit_len] {
+            "ms" => 1.0,
+            "s" => 1_000.0,
+            "m" => 60_000.0,
+            "h" => 3_600_000.0,
+            "d" => 86_400_000.0,
+            _ => return Err(invalid()),
+        };
+        rest = &rest[unit_len..];
+
+        // `as` saturates, values beyond the range of a duration are rejected instead
+        let micros = value * millis_per_unit * 1_000.0;
+        if micros >= i64::MAX as f64 {
+            return Err("Relative time out of range".to_string());
+        }
+        total = total
+            .checked_add(&Duration::microseconds(micros as i64))
+            .ok_or_else(|| "Relative time out of range".to_string())?;
+    }
+
+    Ok(total)
+}
+
+/// Inclusive time window entries are filtered by
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimeRange {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl TimeRange {
+    /// Build a range from optional `from` and `to` expressions, relative expressions are
+    /// resolved against the time span of the log
+    pub fn parse(
+        from: Option<&str>,
+        to: Option<&str>,
+        log_start: Option<DateTime<Utc>>,
+        log_end: Option<DateTime<Utc>>,
+    ) -> Result<Self, String> {
+        let resolve = |text: Option<&str>| -> Result<Option<DateTime<Utc>>, String> {
+            let Some(text) = text.filter(|t| !t.trim().is_empty()) else {
+                return Ok(None);
+            };
+            TimeBound::parse(text)?
+                .resolve(log_start, log_end)?
+                .map(Some)
+                .ok_or_else(|| {
+                    format!(
+                        "Relative time '{}' requires a log with parseable timestamps",
+                        text
+                    )
+                })
+        };
+
+        Ok(Self {
+            from: resolve(from)?,
+            to: resolve(to)?,
+        })
+    }
+
+    /// Whether the range does not restrict anything
+    pub fn is_unbounded(&self) -> bool {
+        self.from.is_none() && self.to.is_none()
+    }
+
+    /// Whether the record falls into the range. If the range is bounded, records without a
+    /// parsed timestamp are outside of it.
+    pub fn contains(&self, record: &LogRecord) -> bool {
+        if self.is_unbounded() {
+            return true;
+        }
+        record.time.is_some_and(|time| {
+            self.from.is_none_or(|from| time >= from) && self.to.is_none_or(|to| time <= to)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
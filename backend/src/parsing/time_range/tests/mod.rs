@@ -0,0 +1 @@
+mod time_range_test;
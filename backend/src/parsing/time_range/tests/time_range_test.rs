@@ -0,0 +1,72 @@
+use super::super::*;
+
+fn utc(raw: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(raw)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+#[test]
+fn test_parse_relative_bounds() {
+    assert_eq!(
+        TimeBound::parse("-15m"),
+        Ok(TimeBound::FromEnd(Duration::minutes(15)))
+    );
+    assert_eq!(
+        TimeBound::parse("+1h30m"),
+        Ok(TimeBound::FromStart(Duration::minutes(90)))
+    );
+    assert_eq!(
+        TimeBound::parse("+1.5s"),
+        Ok(TimeBound::FromStart(Duration::milliseconds(1500)))
+    );
+    assert!(TimeBound::parse("-15").is_err());
+    assert!(TimeBound::parse("+5y").is_err());
+    assert!(TimeBound::parse("-").is_err());
+}
+
+#[test]
+fn test_range_resolves_against_log_span() {
+    let start = utc("2025-11-17T12:00:00Z");
+    let end = utc("2025-11-17T13:00:00Z");
+
+    let range = TimeRange::parse(Some("+30s"), Some("-15m"), Some(start), Some(end)).unwrap();
+
+    assert_eq!(range.from, Some(utc("2025-11-17T12:00:30Z")));
+    assert_eq!(range.to, Some(utc("2025-11-17T12:45:00Z")));
+
+    let range = TimeRange::parse(Some("2025-11-17 12:10:00"), None, None, None).unwrap();
+    assert_eq!(range.from, Some(utc("2025-11-17T12:10:00Z")));
+
+    assert!(TimeRange::parse(Some("-15m"), None, None, None).is_err());
+}
+
+#[test]
+fn test_range_out_of_bounds_is_an_error() {
+    let start = utc("2025-11-17T12:00:00Z");
+    let end = utc("2025-11-17T13:00:00Z");
+
+    let error = TimeRange::parse(Some("+100000000d"), None, Some(start), Some(end));
+    assert_eq!(error, Err("Relative time out of range".to_string()));
+    let error = TimeRange::parse(None, Some("-100000000d"), Some(start), Some(end));
+    assert_eq!(error, Err("Relative time out of range".to_string()));
+    assert!(TimeBound::parse("+1000000000000000000000d").is_err());
+}
+
+#[test]
+fn test_range_contains_records() {
+    let range = TimeRange {
+        from: Some(utc("2025-11-17T12:00:00Z")),
+        to: Some(utc("2025-11-17T12:10:00Z")),
+    };
+    let record = |time: Option<&str>| LogRecord {
+        time: time.map(utc),
+        ..Default::default()
+    };
+
+    assert!(range.contains(&record(Some("2025-11-17T12:00:00Z"))));
+    assert!(range.contains(&record(Some("2025-11-17T12:10:00Z"))));
+    assert!(!range.contains(&record(Some("2025-11-17T12:10:01Z"))));
+    assert!(!range.contains(&record(None)));
+    assert!(TimeRange::default().contains(&record(None)));
+}
@@ -0,0 +1,96 @@
+// chrono
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+/// Formats with an explicit offset, tried after RFC 3339
+const OFFSET_FORMATS: [&str; 4] = [
+    "%Y-%m-%d %H:%M:%S%.f%:z",
+    "%Y-%m-%d %H:%M:%S%.f%z",
+    "%Y-%m-%dT%H:%M:%S%.f%z",
+    "%Y-%m-%d %H:%M:%S%.f %z",
+];
+
+/// Formats without an offset, interpreted as UTC
+const NAIVE_FORMATS: [&str; 4] = [
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y/%m/%d %H:%M:%S%.f",
+    "%d/%b/%Y:%H:%M:%S%.f",
+];
+
+/// Parse a timestamp as found in a log line. Supported are
+/// - RFC 3339 and ISO 8601 with or without an offset, e.g. `2025-11-17 12:50:00.763190172`,
+///   timestamps without an offset are taken as UTC
+/// - epoch seconds, milliseconds, microseconds or nanoseconds, e.g. `1731847800.5`
+/// - syslog `Mon dd hh:mm:ss`, assumed to be in the current year unless that would be more
+///   than a day in the future
+pub fn parse_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+    parse_timestamp_at(raw, Utc::now())
+}
+
+/// Like [`parse_timestamp`], with the current time used to complete syslog timestamps
+pub fn parse_timestamp_at(raw: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+
+    if let Ok(ts) = DateTime::parse_from_rfc3339(raw) {
+        return Some(ts.with_timezone(&Utc));
+    }
+    for format in OFFSET_FORMATS {
+        if let Ok(ts) = DateTime::parse_from_str(raw, format) {
+            return Some(ts.with_timezone(&Utc));
+        }
+    }
+    for format in NAIVE_FORMATS {
+        if let Ok(ts) = NaiveDateTime::parse_from_str(raw, format) {
+            return Some(ts.and_utc());
+        }
+    }
+
+    parse_epoch(raw).or_else(|| parse_syslog(raw, now))
+}
+
+/// Epoch timestamps, the unit is derived from the magnitude of the value
+fn parse_epoch(raw: &str) -> Option<DateTime<Utc>> {
+    let (int_part, frac_part) = raw.split_once('.').unwrap_or((raw, ""));
+    if int_part.is_empty()
+        || !int_part.bytes().all(|b| b.is_ascii_digit())
+        || !frac_part.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let value: i64 = int_part.parse().ok()?;
+    let fraction: f64 = format!("0.{}", frac_part).parse().ok()?;
+    let nanos_per_unit: i64 = match int_part.len() {
+        0..=11 => 1_000_000_000,
+        12..=14 => 1_000_000,
+        15..=17 => 1_000,
+        _ => 1,
+    };
+    let nanos = value
+        .checked_mul(nanos_per_unit)?
+        .checked_add((fraction * nanos_per_unit as f64) as i64)?;
+    Some(Utc.timestamp_nanos(nanos))
+}
+
+/// Syslog `Mon dd hh:mm:ss`, which has no year
+fn parse_syslog(raw: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let parse = |year: i32| {
+        NaiveDateTime::parse_from_str(&format!("{} {}", year, raw), "%Y %b %e %H:%M:%S%.f")
+            .ok()
+            .map(|ts| ts.and_utc())
+    };
+    // check the date separately so that Feb 29 is not rejected in the wrong year
+    NaiveDate::parse_from_str(&format!("2000 {}", raw.get(..6)?), "%Y %b %e").ok()?;
+
+    let year = now.year();
+    match parse(year) {
+        Some(ts) if ts <= now + Duration::days(1) => Some(ts),
+        _ => parse(year - 1),
+    }
+}
+
+#[cfg(test)]
+mod tests;
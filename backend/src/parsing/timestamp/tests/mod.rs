@@ -0,0 +1 @@
+mod timestamp_test;
@@ -0,0 +1,59 @@
+use super::super::*;
+
+fn utc(raw: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(raw)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+#[test]
+fn test_parse_nanosecond_timestamp_as_utc() {
+    assert_eq!(
+        parse_timestamp("2025-11-17 12:50:00.763190172"),
+        Some(utc("2025-11-17T12:50:00.763190172Z"))
+    );
+}
+
+#[test]
+fn test_parse_timestamps_with_offsets() {
+    let expected = utc("2025-11-17T10:50:00Z");
+
+    assert_eq!(parse_timestamp("2025-11-17T12:50:00+02:00"), Some(expected));
+    assert_eq!(parse_timestamp("2025-11-17 12:50:00+02:00"), Some(expected));
+    assert_eq!(parse_timestamp("2025-11-17T12:50:00+0200"), Some(expected));
+    assert_eq!(parse_timestamp("2025-11-17T10:50:00.000Z"), Some(expected));
+}
+
+#[test]
+fn test_parse_epoch_timestamps() {
+    let expected = utc("2024-11-17T12:50:00Z");
+
+    assert_eq!(parse_timestamp("1731847800"), Some(expected));
+    assert_eq!(parse_timestamp("1731847800000"), Some(expected));
+    assert_eq!(
+        parse_timestamp("1731847800.25"),
+        Some(expected + Duration::milliseconds(250))
+    );
+}
+
+#[test]
+fn test_parse_syslog_timestamp_guesses_year() {
+    let now = utc("2025-01-02T00:00:00Z");
+
+    assert_eq!(
+        parse_timestamp_at("Jan  1 22:14:15", now),
+        Some(utc("2025-01-01T22:14:15Z"))
+    );
+    // December is still last year in early January
+    assert_eq!(
+        parse_timestamp_at("Dec 31 23:59:59", now),
+        Some(utc("2024-12-31T23:59:59Z"))
+    );
+}
+
+#[test]
+fn test_parse_invalid_timestamps() {
+    assert_eq!(parse_timestamp(""), None);
+    assert_eq!(parse_timestamp("yesterday"), None);
+    assert_eq!(parse_timestamp("12:50:00"), None);
+}
@@ -7,11 +7,25 @@ export { CancelablePromise, CancelError } from './core/CancelablePromise';
 export { OpenAPI } from './core/OpenAPI';
 export type { OpenAPIConfig } from './core/OpenAPI';
 
+export type { ErrorResponse } from './models/ErrorResponse';
+export type { FieldMapping } from './models/FieldMapping';
+export type { FileFormat } from './models/FileFormat';
+export type { FileSummary } from './models/FileSummary';
 export type { FilteredLogResponse } from './models/FilteredLogResponse';
 export type { FilterRequest } from './models/FilterRequest';
+export type { Hunk } from './models/Hunk';
+export type { LogLine } from './models/LogLine';
 export type { LogSummary } from './models/LogSummary';
+export { MatchMode } from './models/MatchMode';
+export type { MatchSpan } from './models/MatchSpan';
+export type { SeekResponse } from './models/SeekResponse';
+export type { SessionFile } from './models/SessionFile';
+export type { SessionInfo } from './models/SessionInfo';
+export { Severity } from './models/Severity';
 export type { UploadFileBody } from './models/UploadFileBody';
 export type { UploadResponse } from './models/UploadResponse';
 
 export { FilterService } from './services/FilterService';
+export { LogStreamingService } from './services/LogStreamingService';
 export { LogUploadService } from './services/LogUploadService';
+export { SessionsService } from './services/SessionsService';
@@ -0,0 +1,18 @@
+/* generated using openapi-typescript-codegen -- do no edit */
+/* istanbul ignore file */
+/* tslint:disable */
+/* eslint-disable */
+
+/**
+ * Error returned for invalid requests
+ */
+export type ErrorResponse = {
+    /**
+     * Description of the error
+     */
+    error: string;
+    /**
+     * Character position in the offending parameter the error refers to (0-based)
+     */
+    position?: number | null;
+};
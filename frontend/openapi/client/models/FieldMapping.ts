@@ -0,0 +1,28 @@
+/* generated using openapi-typescript-codegen -- do no edit */
+/* istanbul ignore file */
+/* tslint:disable */
+/* eslint-disable */
+
+/**
+ * Which JSON keys hold the well-known parts of a record. For each part the first key present
+ * in the object is used. Nested objects are flattened, so `log.level` addresses
+ * `{"log": {"level": ...}}`.
+ */
+export type FieldMapping = {
+    /**
+     * Candidate keys for the domain
+     */
+    domain?: Array<string>;
+    /**
+     * Candidate keys for the log level
+     */
+    level?: Array<string>;
+    /**
+     * Candidate keys for the message
+     */
+    message?: Array<string>;
+    /**
+     * Candidate keys for the timestamp
+     */
+    timestamp?: Array<string>;
+};
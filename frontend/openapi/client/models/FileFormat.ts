@@ -0,0 +1,22 @@
+/* generated using openapi-typescript-codegen -- do no edit */
+/* istanbul ignore file */
+/* tslint:disable */
+/* eslint-disable */
+
+/**
+ * Log format used to parse one uploaded file
+ */
+export type FileFormat = {
+    /**
+     * Share of sampled lines matching the format, between 0 and 1
+     */
+    confidence: number;
+    /**
+     * Name of the log format used to parse the file
+     */
+    format: string;
+    /**
+     * Name entries of the file are tagged with
+     */
+    source: string;
+};
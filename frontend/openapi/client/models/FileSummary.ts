@@ -0,0 +1,34 @@
+/* generated using openapi-typescript-codegen -- do no edit */
+/* istanbul ignore file */
+/* tslint:disable */
+/* eslint-disable */
+
+/**
+ * Statistics of one file of a session
+ */
+export type FileSummary = {
+    /**
+     * Name of the log format used to parse the file
+     */
+    format: string;
+    /**
+     * Count of log entries per normalized level in severity order
+     */
+    levels: Record<string, number>;
+    /**
+     * Name entries of the file are tagged with
+     */
+    source: string;
+    /**
+     * Parsed timestamp of the earliest log entry in the file
+     */
+    start_time?: string | null;
+    /**
+     * Parsed timestamp of the latest log entry in the file
+     */
+    stop_time?: string | null;
+    /**
+     * Number of log entries in the file
+     */
+    total_lines: number;
+};
@@ -3,20 +3,86 @@
 /* tslint:disable */
 /* eslint-disable */
 
+import type { FieldMapping } from './FieldMapping';
+import type { MatchMode } from './MatchMode';
+import type { Severity } from './Severity';
+
 /**
  * Request payload for filtering logs
  */
 export type FilterRequest = {
+    /**
+     * Optional number of entries to include after each match, overrides `context`
+     */
+    after?: number | null;
+    /**
+     * Optional number of entries to include before each match, overrides `context`
+     */
+    before?: number | null;
+    /**
+     * Optional number of surrounding entries to include around each match
+     */
+    context?: number | null;
     /**
      * Optional list of domains to filter by
      */
     domains?: any[] | null;
     /**
-     * Optional list of log levels to filter by
+     * Optional list of domains to hide, takes priority over all other filters
+     */
+    exclude_domains?: any[] | null;
+    /**
+     * Optional list of keywords to hide, matched according to `match_mode`
+     */
+    exclude_keywords?: any[] | null;
+    /**
+     * Optional list of log levels to hide, takes priority over all other filters
+     */
+    exclude_levels?: any[] | null;
+    /**
+     * Optional mapping of JSON keys for the "ndjson" format
+     */
+    field_mapping?: FieldMapping | null;
+    /**
+     * Optional conditions on additional fields, e.g. `status>=500` or `component=net`
+     */
+    fields?: any[] | null;
+    /**
+     * Optional name of the log format, defaults to "bracketed"
+     */
+    format?: string | null;
+    /**
+     * Optional start of the time window, an absolute timestamp or relative to the
+     * start of the log, e.g. "+30s"
+     */
+    from?: string | null;
+    /**
+     * Optional list of keywords, entries containing any of them are kept
+     */
+    keywords?: any[] | null;
+    /**
+     * Optional list of log levels to filter by, aliases like "warning" are normalized
      */
     levels?: any[] | null;
     /**
      * Full log text
      */
     log_text: string;
+    /**
+     * How keywords in the query are matched, defaults to literal
+     */
+    match_mode?: MatchMode | null;
+    /**
+     * Optional minimum severity, e.g. "WARN" keeps WARN, ERROR and FATAL entries
+     */
+    min_level?: Severity | null;
+    /**
+     * Optional boolean query, e.g. `level:ERROR AND (domain:network OR domain:core) AND NOT "retrying"`
+     */
+    query?: string | null;
+    /**
+     * Optional end of the time window, an absolute timestamp or relative to the
+     * end of the log, e.g. "-15m"
+     */
+    to?: string | null;
 };
@@ -3,6 +3,7 @@
 /* tslint:disable */
 /* eslint-disable */
 
+import type { Hunk } from './Hunk';
 import type { LogSummary } from './LogSummary';
 
 /**
@@ -10,9 +11,10 @@ import type { LogSummary } from './LogSummary';
  */
 export type FilteredLogResponse = {
     /**
-     * Lines of the log that match the filter
+     * Matching entries with their context, grouped into hunks of adjacent entries. Without
+     * context all matches are in one hunk.
      */
-    filtered_lines: Array<string>;
+    hunks: Array<Hunk>;
     /**
      * Summary of the filtered log
      */
@@ -0,0 +1,13 @@
+/* generated using openapi-typescript-codegen -- do no edit */
+/* istanbul ignore file */
+/* tslint:disable */
+/* eslint-disable */
+
+import type { LogLine } from './LogLine';
+
+/**
+ * Adjacent entries, i.e. matches together with their context
+ */
+export type Hunk = {
+    lines: Array<LogLine>;
+};
@@ -0,0 +1,52 @@
+/* generated using openapi-typescript-codegen -- do no edit */
+/* istanbul ignore file */
+/* tslint:disable */
+/* eslint-disable */
+
+import type { MatchSpan } from './MatchSpan';
+
+/**
+ * A log entry as sent to clients
+ */
+export type LogLine = {
+    /**
+     * Byte offset of the entry in the file
+     */
+    byte_offset: number;
+    /**
+     * Whether the entry is only included as context of a match
+     */
+    context: boolean;
+    /**
+     * Domain the entry was logged from
+     */
+    domain?: string | null;
+    /**
+     * Keyword and regex hits in the message, sorted and not overlapping
+     */
+    highlights: Array<MatchSpan>;
+    /**
+     * Normalized log level
+     */
+    level?: string | null;
+    /**
+     * Raw text of the entry, multi-line entries are joined by newlines
+     */
+    line: string;
+    /**
+     * Line number of the entry in the file (1-based)
+     */
+    line_number: number;
+    /**
+     * Message including continuation lines
+     */
+    message: string;
+    /**
+     * Name of the file the entry belongs to, for entries of an uploaded session
+     */
+    source?: string | null;
+    /**
+     * Timestamp as it appears in the entry
+     */
+    ts?: string | null;
+};
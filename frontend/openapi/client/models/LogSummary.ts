@@ -3,25 +3,51 @@
 /* tslint:disable */
 /* eslint-disable */
 
+import type { FileSummary } from './FileSummary';
+
 export type LogSummary = {
     /**
-     * Count of log entries per level, e.g., {"INFO": 123, "WARN": 5}
+     * Time between the earliest and the latest log entry in seconds
+     */
+    duration_seconds?: number | null;
+    /**
+     * Count of log entries per syslog facility, e.g., {"auth": 12, "cron": 3}
+     */
+    facilities: Record<string, number>;
+    /**
+     * Statistics per file of a session with several files
+     */
+    files?: Array<FileSummary>;
+    /**
+     * Count of log entries per normalized level in severity order, e.g., {"INFO": 123, "WARN": 5}
      */
     levels: Record<string, number>;
     /**
-     * Timestamp of the first log entry
+     * Parsed timestamp of the earliest log entry
+     */
+    start_time?: string | null;
+    /**
+     * Timestamp of the earliest log entry as it appears in the log
      */
     start_timestamp?: string | null;
     /**
-     * Timestamp of the last log entry
+     * Parsed timestamp of the latest log entry
+     */
+    stop_time?: string | null;
+    /**
+     * Timestamp of the latest log entry as it appears in the log
      */
     stop_timestamp?: string | null;
     /**
-     * Total number of lines in the log
+     * Total number of log entries, an entry spanning multiple lines is counted once
      */
     total_lines: number;
     /**
      * List of unique domains found in the log
      */
     unique_domains: Array<string>;
+    /**
+     * List of unique hosts found in the log
+     */
+    unique_hosts: Array<string>;
 };
@@ -0,0 +1,14 @@
+/* generated using openapi-typescript-codegen -- do no edit */
+/* istanbul ignore file */
+/* tslint:disable */
+/* eslint-disable */
+
+/**
+ * How keywords are matched against messages
+ */
+export enum MatchMode {
+    LITERAL = 'literal',
+    IGNORE_CASE = 'ignore_case',
+    REGEX = 'regex',
+    WORD = 'word',
+}
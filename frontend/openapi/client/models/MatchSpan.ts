@@ -0,0 +1,19 @@
+/* generated using openapi-typescript-codegen -- do no edit */
+/* istanbul ignore file */
+/* tslint:disable */
+/* eslint-disable */
+
+/**
+ * Range of a keyword hit in a message, in UTF-16 code units like JavaScript string indices, so
+ * `message.slice(start, end)` is the hit
+ */
+export type MatchSpan = {
+    /**
+     * Position after the last code unit of the hit
+     */
+    end: number;
+    /**
+     * Position of the first code unit of the hit (0-based)
+     */
+    start: number;
+};
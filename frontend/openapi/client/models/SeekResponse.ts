@@ -0,0 +1,27 @@
+/* generated using openapi-typescript-codegen -- do no edit */
+/* istanbul ignore file */
+/* tslint:disable */
+/* eslint-disable */
+
+/**
+ * Position of the first entry at or after a timestamp
+ */
+export type SeekResponse = {
+    /**
+     * Byte offset of the entry in its file. A `cursor` resumes strictly after the entry at its
+     * offset, to stream from this entry on pass the timestamp as `start_at` instead.
+     */
+    byte_offset: number;
+    /**
+     * Line number of the entry in the file (1-based)
+     */
+    line_number: number;
+    /**
+     * Name of the file the entry belongs to
+     */
+    source: string;
+    /**
+     * Timestamp as it appears in the entry
+     */
+    ts?: string | null;
+};
@@ -0,0 +1,34 @@
+/* generated using openapi-typescript-codegen -- do no edit */
+/* istanbul ignore file */
+/* tslint:disable */
+/* eslint-disable */
+
+/**
+ * One uploaded file of a session
+ */
+export type SessionFile = {
+    /**
+     * Name of the file as uploaded
+     */
+    file_name?: string | null;
+    /**
+     * Name of the log format used to parse the file
+     */
+    format: string;
+    /**
+     * Number of lines in the file
+     */
+    line_count: number;
+    /**
+     * SHA-256 of the file content, hex encoded
+     */
+    sha256: string;
+    /**
+     * Size of the file in bytes
+     */
+    size: number;
+    /**
+     * Name entries of the file are tagged with, can be used to filter by file
+     */
+    source: string;
+};
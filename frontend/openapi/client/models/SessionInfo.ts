@@ -0,0 +1,41 @@
+/* generated using openapi-typescript-codegen -- do no edit */
+/* istanbul ignore file */
+/* tslint:disable */
+/* eslint-disable */
+
+import type { LogSummary } from './LogSummary';
+import type { SessionFile } from './SessionFile';
+
+/**
+ * An uploaded log a user can return to
+ */
+export type SessionInfo = {
+    /**
+     * Uploaded files, their entries are merged by timestamp
+     */
+    files: Array<SessionFile>;
+    /**
+     * When the session was last used
+     */
+    last_access: string;
+    /**
+     * Total number of lines in the files
+     */
+    line_count: number;
+    /**
+     * Unique session identifier
+     */
+    session_id: string;
+    /**
+     * Total size of the files in bytes
+     */
+    size: number;
+    /**
+     * Summary of all files with statistics per file
+     */
+    summary: LogSummary;
+    /**
+     * When the files were uploaded
+     */
+    uploaded_at: string;
+};
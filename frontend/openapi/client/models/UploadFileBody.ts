@@ -3,12 +3,22 @@
 /* tslint:disable */
 /* eslint-disable */
 
+import type { FieldMapping } from './FieldMapping';
+
 /**
  * Dummy type to document multipart request body
  */
 export type UploadFileBody = {
     /**
-     * The log file to upload
+     * Optional field mapping (JSON) for the "ndjson" format
+     */
+    field_mapping?: FieldMapping | null;
+    /**
+     * The log file to upload, repeat the field to upload several files into one session
      */
     file: Array<Blob>;
+    /**
+     * Optional name of the log format, detected automatically if omitted
+     */
+    format?: string | null;
 };
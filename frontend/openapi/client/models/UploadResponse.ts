@@ -3,9 +3,28 @@
 /* tslint:disable */
 /* eslint-disable */
 
+import type { FileFormat } from './FileFormat';
 import type { LogSummary } from './LogSummary';
 
 export type UploadResponse = {
+    /**
+     * Share of sampled lines of the first file matching its format, between 0 and 1
+     */
+    confidence: number;
+    /**
+     * Log format of each uploaded file, in upload order
+     */
+    files: Array<FileFormat>;
+    /**
+     * Name of the log format used to parse the first file, see `files` for the others
+     */
+    format: string;
+    /**
+     * Unique session identifier for the uploaded log
+     */
     session_id: string;
+    /**
+     * Summary of the uploaded log
+     */
     summary: LogSummary;
 };
@@ -14,7 +14,7 @@ export class FilterService {
     /**
      * Filter log lines and produce a summary
      * @param requestBody 
-     * @returns FilteredLogResponse Filtered log lines with summary
+     * @returns FilteredLogResponse Filtered log entries grouped into hunks with their context, and a summary
      * @throws ApiError
      */
     public static filterHandler(
@@ -25,6 +25,10 @@ requestBody: FilterRequest,
             url: '/filter',
             body: requestBody,
             mediaType: 'application/json',
+            errors: {
+                400: `Invalid query, unknown log format, invalid field mapping, keyword regex, field filter or time window. Query errors include the position they refer to`,
+                422: `Unknown minimum log level`,
+            },
         });
     }
 
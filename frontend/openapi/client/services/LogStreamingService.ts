@@ -0,0 +1,39 @@
+/* generated using openapi-typescript-codegen -- do no edit */
+/* istanbul ignore file */
+/* tslint:disable */
+/* eslint-disable */
+import type { SeekResponse } from '../models/SeekResponse';
+
+import type { CancelablePromise } from '../core/CancelablePromise';
+import { OpenAPI } from '../core/OpenAPI';
+import { request as __request } from '../core/request';
+
+export class LogStreamingService {
+
+    /**
+     * @param id Log session ID
+     * @param ts Timestamp to jump to. Absolute timestamp or relative to the start (`+30s`) or end (`-15m`) of the log
+     * @returns SeekResponse Position of the first entry at or after the timestamp. With several files, the earliest such entry of all files
+     * @throws ApiError
+     */
+    public static seekHandler(
+id: string,
+ts: string,
+): CancelablePromise<SeekResponse> {
+        return __request(OpenAPI, {
+            method: 'GET',
+            url: '/sessions/{id}/seek',
+            path: {
+                'id': id,
+            },
+            query: {
+                'ts': ts,
+            },
+            errors: {
+                400: `Invalid timestamp`,
+                404: `Session ID not found or no entry at or after the timestamp`,
+            },
+        });
+    }
+
+}
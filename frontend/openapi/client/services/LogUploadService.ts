@@ -12,9 +12,9 @@ import { request as __request } from '../core/request';
 export class LogUploadService {
 
     /**
-     * Upload and parse a log file
-     * @param requestBody Log file to upload. Only the first file in the multipart request is processed. Maximum size: 1 GB
-     * @returns UploadResponse Upload successful, returns session ID and log summary
+     * Upload and parse one or more log files
+     * @param requestBody Log files to upload into one session, their entries are merged by timestamp when streamed. Maximum size: 1 GB. The log format of each file is detected from its first lines unless a `format` or `field_mapping` field is given, which then applies to all files
+     * @returns UploadResponse Upload successful, returns session ID, the log format of each file and log summary with statistics per file
      * @throws ApiError
      */
     public static uploadHandler(
@@ -26,8 +26,9 @@ requestBody: UploadFileBody,
             body: requestBody,
             mediaType: 'application/json',
             errors: {
-                400: `No file uploaded or invalid multipart request`,
+                400: `No file uploaded, unknown log format or invalid multipart request`,
                 500: `Internal server error while creating directories, writing, or reading the file`,
+                507: `The files do not fit into the disk quota, even after evicting other sessions`,
             },
         });
     }
@@ -0,0 +1,49 @@
+/* generated using openapi-typescript-codegen -- do no edit */
+/* istanbul ignore file */
+/* tslint:disable */
+/* eslint-disable */
+import type { SessionInfo } from '../models/SessionInfo';
+
+import type { CancelablePromise } from '../core/CancelablePromise';
+import { OpenAPI } from '../core/OpenAPI';
+import { request as __request } from '../core/request';
+
+export class SessionsService {
+
+    /**
+     * Lists the IDs of all sessions, which gives access to every uploaded log. Servers shared by several users turn this off with `SESSION_LISTING_ENABLED=false`.
+     * @returns Array<SessionInfo> All stored sessions, most recently used first
+     * @throws ApiError
+     */
+    public static listSessions(
+): CancelablePromise<Array<SessionInfo>> {
+        return __request(OpenAPI, {
+            method: 'GET',
+            url: '/sessions',
+            errors: {
+                403: `Session listing is disabled by `SESSION_LISTING_ENABLED=false``,
+            },
+        });
+    }
+
+    /**
+     * @param id Log session ID
+     * @returns SessionInfo Metadata and summary of the session, counts as an access
+     * @throws ApiError
+     */
+    public static getSession(
+id: string,
+): CancelablePromise<SessionInfo> {
+        return __request(OpenAPI, {
+            method: 'GET',
+            url: '/sessions/{id}',
+            path: {
+                'id': id,
+            },
+            errors: {
+                404: `Session ID not found`,
+            },
+        });
+    }
+
+}